@@ -0,0 +1,139 @@
+//! This module provides the serde helpers that preserve the array kinds, to
+//! be used with `#[serde(with = "...")]`.
+//!
+//! Serde has no notion of arrays, so a `Vec<i32>` is serialized as a list of
+//! ints by default, whereas minecraft writes e.g. the UUIDs of entities as an
+//! [`Kind::IntArray`]. The helpers force the array
+//! kinds on serialization, and accept either a list or an array on
+//! deserialization:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Entity {
+//!     #[serde(rename = "UUID", with = "znbt::array::int_array")]
+//!     uuid: [i32; 4],
+//!     #[serde(with = "znbt::array::long_array")]
+//!     heightmap: Vec<i64>,
+//! }
+//! ```
+//!
+//! The helpers accept any type that is [`AsRef`] of a slice on serialization,
+//! and [`TryFrom`] a vector on deserialization, e.g., a vector, a fixed-size
+//! array, or the [`IntArray`](crate::value::IntArray) of the value tree.
+//!
+//! For other serde formats, the helpers are transparent, i.e., the arrays are
+//! serialized as sequences.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+
+use crate::kind::Kind;
+
+/// The name of the newtype struct that wraps a byte array.
+pub(crate) const BYTE_ARRAY: &str = "__znbt_ByteArray";
+/// The name of the newtype struct that wraps an int array.
+pub(crate) const INT_ARRAY: &str = "__znbt_IntArray";
+/// The name of the newtype struct that wraps a long array.
+pub(crate) const LONG_ARRAY: &str = "__znbt_LongArray";
+
+/// Returns the array kind wrapped by the newtype struct of the name.
+pub(crate) fn kind_of(name: &str) -> Option<Kind> {
+    match name {
+        BYTE_ARRAY => Some(Kind::ByteArray),
+        INT_ARRAY => Some(Kind::IntArray),
+        LONG_ARRAY => Some(Kind::LongArray),
+        _ => None,
+    }
+}
+
+macro_rules! array_module {
+    ($(#[$attr:meta])* $module:ident, $name:ident, $element:ty, $expecting:literal) => {
+        $(#[$attr])*
+        pub mod $module {
+            use super::*;
+
+            /// Serializes the array as the array kind.
+            ///
+            /// # Errors
+            ///
+            /// This function returns an error if the serializer fails.
+            pub fn serialize<T, S>(array: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: AsRef<[$element]> + ?Sized,
+                S: Serializer,
+            {
+                serializer.serialize_newtype_struct($name, array.as_ref())
+            }
+
+            /// Deserializes the array from either a list or an array.
+            ///
+            /// # Errors
+            ///
+            /// This function returns an error if the deserializer fails, or
+            /// the array cannot be converted into the type, e.g., the length
+            /// of a fixed-size array mismatches.
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: TryFrom<Vec<$element>>,
+                D: Deserializer<'de>,
+            {
+                let vec = deserializer.deserialize_newtype_struct($name, ArrayVisitor {
+                    expecting: $expecting,
+                    marker: PhantomData,
+                })?;
+                let len = vec.len();
+                T::try_from(vec).map_err(|_| de::Error::invalid_length(len, &$expecting))
+            }
+        }
+    };
+}
+
+array_module! {
+    /// The serde helper of [`Kind::ByteArray`], see the [module](super) docs.
+    byte_array, BYTE_ARRAY, i8, "a byte array"
+}
+
+array_module! {
+    /// The serde helper of [`Kind::IntArray`], see the [module](super) docs.
+    int_array, INT_ARRAY, i32, "an int array"
+}
+
+array_module! {
+    /// The serde helper of [`Kind::LongArray`], see the [module](super) docs.
+    long_array, LONG_ARRAY, i64, "a long array"
+}
+
+/// Visits the newtype struct that wraps an array, or the array itself.
+struct ArrayVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ArrayVisitor<T> {
+    type Value = Vec<T>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Vec<T>, D::Error> {
+        Vec::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<T>, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(element) = seq.next_element()? {
+            vec.push(element);
+        }
+        Ok(vec)
+    }
+}
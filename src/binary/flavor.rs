@@ -0,0 +1,474 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::str;
+
+use crate::binary::Write;
+use crate::error::NbtError;
+use crate::mutf8;
+
+/// The encoding of numbers and length prefixes, which differs between the
+/// editions of minecraft.
+///
+/// The tag dispatch is shared by all the flavors, i.e., every flavor uses the
+/// same twelve [`Kind`](crate::kind::Kind) IDs and the same tag structure.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Flavor: Copy + private::Sealed {
+    /// Reads a signed 16-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_i16(self, input: &mut &[u8]) -> Result<i16, NbtError>;
+
+    /// Reads a signed 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_i32(self, input: &mut &[u8]) -> Result<i32, NbtError>;
+
+    /// Reads a signed 64-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_i64(self, input: &mut &[u8]) -> Result<i64, NbtError>;
+
+    /// Reads a 32-bit floating-point number.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_f32(self, input: &mut &[u8]) -> Result<f32, NbtError>;
+
+    /// Reads a 64-bit floating-point number.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_f64(self, input: &mut &[u8]) -> Result<f64, NbtError>;
+
+    /// Reads the length prefix of an array or list.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short, or the
+    /// length is negative.
+    fn read_len(self, input: &mut &[u8]) -> Result<usize, NbtError> {
+        let len = self.read_i32(input)?;
+        usize::try_from(len).map_err(|_| NbtError::NegativeLength(len))
+    }
+
+    /// Reads the length prefix of a string, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is too short.
+    fn read_str_len(self, input: &mut &[u8]) -> Result<usize, NbtError> {
+        Ok(usize::from(self.read_i16(input)? as u16))
+    }
+
+    /// Decodes the bytes of a string, borrowing them if possible.
+    ///
+    /// The default implementation decodes the bytes as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the bytes are ill-formed.
+    fn decode_str(self, bytes: &[u8]) -> Result<Cow<'_, str>, NbtError> {
+        match str::from_utf8(bytes) {
+            Ok(string) => Ok(Cow::Borrowed(string)),
+            Err(_) => Err(NbtError::InvalidString),
+        }
+    }
+
+    /// Decodes the bytes of a string, replacing any ill-formed sequence with
+    /// `U+FFFD`, see [`Lossy`].
+    ///
+    /// The default implementation decodes the bytes as UTF-8.
+    #[must_use]
+    fn decode_str_lossy(self, bytes: &[u8]) -> Cow<'_, str> {
+        String::from_utf8_lossy(bytes)
+    }
+
+    /// Encodes a string into bytes, borrowing it if possible.
+    ///
+    /// The default implementation encodes the string as UTF-8.
+    #[must_use]
+    fn encode_str(self, string: &str) -> Cow<'_, [u8]> {
+        Cow::Borrowed(string.as_bytes())
+    }
+
+    /// Writes a signed 16-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails.
+    fn write_i16<W: Write + ?Sized>(self, output: &mut W, value: i16) -> Result<(), NbtError>;
+
+    /// Writes a signed 32-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails.
+    fn write_i32<W: Write + ?Sized>(self, output: &mut W, value: i32) -> Result<(), NbtError>;
+
+    /// Writes a signed 64-bit integer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails.
+    fn write_i64<W: Write + ?Sized>(self, output: &mut W, value: i64) -> Result<(), NbtError>;
+
+    /// Writes a 32-bit floating-point number.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails.
+    fn write_f32<W: Write + ?Sized>(self, output: &mut W, value: f32) -> Result<(), NbtError>;
+
+    /// Writes a 64-bit floating-point number.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails.
+    fn write_f64<W: Write + ?Sized>(self, output: &mut W, value: f64) -> Result<(), NbtError>;
+
+    /// Writes the length prefix of an array or list.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails, or the length is
+    /// out of range.
+    fn write_len<W: Write + ?Sized>(self, output: &mut W, len: usize) -> Result<(), NbtError> {
+        let len = i32::try_from(len).map_err(|_| NbtError::LengthOverflow(len))?;
+        self.write_i32(output, len)
+    }
+
+    /// Writes the length prefix of a string, in bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the output fails, or the length is
+    /// out of range.
+    fn write_str_len<W: Write + ?Sized>(self, output: &mut W, len: usize) -> Result<(), NbtError> {
+        let len = u16::try_from(len).map_err(|_| NbtError::LengthOverflow(len))?;
+        self.write_i16(output, len as i16)
+    }
+}
+
+/// Splits the first `len` bytes off the input.
+pub(crate) fn take<'de>(input: &mut &'de [u8], len: usize) -> Result<&'de [u8], NbtError> {
+    if input.len() < len {
+        return Err(NbtError::UnexpectedEof);
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Splits the first `N` bytes off the input.
+pub(crate) fn take_array<const N: usize>(input: &mut &[u8]) -> Result<[u8; N], NbtError> {
+    Ok(take(input, N)?.try_into().unwrap())
+}
+
+/// Implements the fixed-size numbers with the given byte order.
+macro_rules! impl_fixed {
+    ($from_bytes:ident, $to_bytes:ident) => {
+        #[inline]
+        fn read_i16(self, input: &mut &[u8]) -> Result<i16, NbtError> {
+            Ok(i16::$from_bytes(take_array(input)?))
+        }
+
+        #[inline]
+        fn read_i32(self, input: &mut &[u8]) -> Result<i32, NbtError> {
+            Ok(i32::$from_bytes(take_array(input)?))
+        }
+
+        #[inline]
+        fn read_i64(self, input: &mut &[u8]) -> Result<i64, NbtError> {
+            Ok(i64::$from_bytes(take_array(input)?))
+        }
+
+        #[inline]
+        fn read_f32(self, input: &mut &[u8]) -> Result<f32, NbtError> {
+            Ok(f32::$from_bytes(take_array(input)?))
+        }
+
+        #[inline]
+        fn read_f64(self, input: &mut &[u8]) -> Result<f64, NbtError> {
+            Ok(f64::$from_bytes(take_array(input)?))
+        }
+
+        #[inline]
+        fn write_i16<W: Write + ?Sized>(self, output: &mut W, value: i16) -> Result<(), NbtError> {
+            output.write_all(&value.$to_bytes())
+        }
+
+        #[inline]
+        fn write_i32<W: Write + ?Sized>(self, output: &mut W, value: i32) -> Result<(), NbtError> {
+            output.write_all(&value.$to_bytes())
+        }
+
+        #[inline]
+        fn write_i64<W: Write + ?Sized>(self, output: &mut W, value: i64) -> Result<(), NbtError> {
+            output.write_all(&value.$to_bytes())
+        }
+
+        #[inline]
+        fn write_f32<W: Write + ?Sized>(self, output: &mut W, value: f32) -> Result<(), NbtError> {
+            output.write_all(&value.$to_bytes())
+        }
+
+        #[inline]
+        fn write_f64<W: Write + ?Sized>(self, output: &mut W, value: f64) -> Result<(), NbtError> {
+            output.write_all(&value.$to_bytes())
+        }
+    };
+}
+
+/// The flavor of minecraft Java Edition, where all the numbers are big-endian,
+/// and the strings are encoded in [Modified UTF-8](crate::mutf8).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Java;
+
+impl Flavor for Java {
+    impl_fixed!(from_be_bytes, to_be_bytes);
+
+    #[inline]
+    fn decode_str(self, bytes: &[u8]) -> Result<Cow<'_, str>, NbtError> {
+        mutf8::decode(bytes).map_err(|_| NbtError::InvalidString)
+    }
+
+    #[inline]
+    fn decode_str_lossy(self, bytes: &[u8]) -> Cow<'_, str> {
+        mutf8::decode_lossy(bytes)
+    }
+
+    #[inline]
+    fn encode_str(self, string: &str) -> Cow<'_, [u8]> {
+        mutf8::encode(string)
+    }
+}
+
+/// The flavor of minecraft Bedrock Edition files, e.g., `level.dat` and
+/// `.mcstructure`, where all the numbers are little-endian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bedrock;
+
+impl Flavor for Bedrock {
+    impl_fixed!(from_le_bytes, to_le_bytes);
+}
+
+/// The flavor of minecraft Bedrock Edition network protocol, where the numbers
+/// are little-endian except:
+///
+/// - Ints and longs are ZigZag-encoded VarInts.
+/// - Length prefixes of arrays and lists are ZigZag-encoded VarInts.
+/// - Length prefixes of strings are unsigned VarInts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BedrockNetwork;
+
+impl Flavor for BedrockNetwork {
+    #[inline]
+    fn read_i16(self, input: &mut &[u8]) -> Result<i16, NbtError> {
+        Bedrock.read_i16(input)
+    }
+
+    #[inline]
+    fn read_i32(self, input: &mut &[u8]) -> Result<i32, NbtError> {
+        let value = read_var(input, 32)? as u32;
+        Ok((value >> 1) as i32 ^ -((value & 1) as i32))
+    }
+
+    #[inline]
+    fn read_i64(self, input: &mut &[u8]) -> Result<i64, NbtError> {
+        let value = read_var(input, 64)?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    #[inline]
+    fn read_f32(self, input: &mut &[u8]) -> Result<f32, NbtError> {
+        Bedrock.read_f32(input)
+    }
+
+    #[inline]
+    fn read_f64(self, input: &mut &[u8]) -> Result<f64, NbtError> {
+        Bedrock.read_f64(input)
+    }
+
+    #[inline]
+    fn read_str_len(self, input: &mut &[u8]) -> Result<usize, NbtError> {
+        let len = read_var(input, 32)?;
+        usize::try_from(len).map_err(|_| NbtError::InvalidVarInt)
+    }
+
+    #[inline]
+    fn write_i16<W: Write + ?Sized>(self, output: &mut W, value: i16) -> Result<(), NbtError> {
+        Bedrock.write_i16(output, value)
+    }
+
+    #[inline]
+    fn write_i32<W: Write + ?Sized>(self, output: &mut W, value: i32) -> Result<(), NbtError> {
+        write_var(output, ((value << 1) ^ (value >> 31)) as u32 as u64)
+    }
+
+    #[inline]
+    fn write_i64<W: Write + ?Sized>(self, output: &mut W, value: i64) -> Result<(), NbtError> {
+        write_var(output, ((value << 1) ^ (value >> 63)) as u64)
+    }
+
+    #[inline]
+    fn write_f32<W: Write + ?Sized>(self, output: &mut W, value: f32) -> Result<(), NbtError> {
+        Bedrock.write_f32(output, value)
+    }
+
+    #[inline]
+    fn write_f64<W: Write + ?Sized>(self, output: &mut W, value: f64) -> Result<(), NbtError> {
+        Bedrock.write_f64(output, value)
+    }
+
+    #[inline]
+    fn write_str_len<W: Write + ?Sized>(self, output: &mut W, len: usize) -> Result<(), NbtError> {
+        let len = u32::try_from(len).map_err(|_| NbtError::LengthOverflow(len))?;
+        write_var(output, u64::from(len))
+    }
+}
+
+/// Reads an unsigned VarInt of at most `bits` bits, i.e., 7 bits per byte
+/// with the most significant bit as the continuation flag.
+fn read_var(input: &mut &[u8], bits: u32) -> Result<u64, NbtError> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let [byte] = take_array(input)?;
+        if shift >= bits || (shift > 0 && u64::from(byte & 0x7f) >> (bits - shift) != 0) {
+            return Err(NbtError::InvalidVarInt);
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+/// Writes an unsigned VarInt, see [`read_var`].
+fn write_var<W: Write + ?Sized>(output: &mut W, mut value: u64) -> Result<(), NbtError> {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            return output.write_all(&buf[..=len]);
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+/// A flavor that decodes ill-formed strings lossily, replacing any ill-formed
+/// sequence with `U+FFFD`, instead of returning an error.
+///
+/// Everything else is delegated to the inner flavor, e.g., `Lossy(Java)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Lossy<F>(pub F);
+
+impl<F: Flavor> Flavor for Lossy<F> {
+    #[inline]
+    fn read_i16(self, input: &mut &[u8]) -> Result<i16, NbtError> {
+        self.0.read_i16(input)
+    }
+
+    #[inline]
+    fn read_i32(self, input: &mut &[u8]) -> Result<i32, NbtError> {
+        self.0.read_i32(input)
+    }
+
+    #[inline]
+    fn read_i64(self, input: &mut &[u8]) -> Result<i64, NbtError> {
+        self.0.read_i64(input)
+    }
+
+    #[inline]
+    fn read_f32(self, input: &mut &[u8]) -> Result<f32, NbtError> {
+        self.0.read_f32(input)
+    }
+
+    #[inline]
+    fn read_f64(self, input: &mut &[u8]) -> Result<f64, NbtError> {
+        self.0.read_f64(input)
+    }
+
+    #[inline]
+    fn read_len(self, input: &mut &[u8]) -> Result<usize, NbtError> {
+        self.0.read_len(input)
+    }
+
+    #[inline]
+    fn read_str_len(self, input: &mut &[u8]) -> Result<usize, NbtError> {
+        self.0.read_str_len(input)
+    }
+
+    #[inline]
+    fn decode_str(self, bytes: &[u8]) -> Result<Cow<'_, str>, NbtError> {
+        Ok(self.0.decode_str_lossy(bytes))
+    }
+
+    #[inline]
+    fn decode_str_lossy(self, bytes: &[u8]) -> Cow<'_, str> {
+        self.0.decode_str_lossy(bytes)
+    }
+
+    #[inline]
+    fn encode_str(self, string: &str) -> Cow<'_, [u8]> {
+        self.0.encode_str(string)
+    }
+
+    #[inline]
+    fn write_i16<W: Write + ?Sized>(self, output: &mut W, value: i16) -> Result<(), NbtError> {
+        self.0.write_i16(output, value)
+    }
+
+    #[inline]
+    fn write_i32<W: Write + ?Sized>(self, output: &mut W, value: i32) -> Result<(), NbtError> {
+        self.0.write_i32(output, value)
+    }
+
+    #[inline]
+    fn write_i64<W: Write + ?Sized>(self, output: &mut W, value: i64) -> Result<(), NbtError> {
+        self.0.write_i64(output, value)
+    }
+
+    #[inline]
+    fn write_f32<W: Write + ?Sized>(self, output: &mut W, value: f32) -> Result<(), NbtError> {
+        self.0.write_f32(output, value)
+    }
+
+    #[inline]
+    fn write_f64<W: Write + ?Sized>(self, output: &mut W, value: f64) -> Result<(), NbtError> {
+        self.0.write_f64(output, value)
+    }
+
+    #[inline]
+    fn write_len<W: Write + ?Sized>(self, output: &mut W, len: usize) -> Result<(), NbtError> {
+        self.0.write_len(output, len)
+    }
+
+    #[inline]
+    fn write_str_len<W: Write + ?Sized>(self, output: &mut W, len: usize) -> Result<(), NbtError> {
+        self.0.write_str_len(output, len)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl<F: Sealed> Sealed for super::Lossy<F> {}
+    impl Sealed for super::Java {}
+    impl Sealed for super::Bedrock {}
+    impl Sealed for super::BedrockNetwork {}
+}
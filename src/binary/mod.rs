@@ -0,0 +1,73 @@
+//! This module provides the encoder and decoder of the binary NBT format.
+//!
+//! A binary NBT file consists of one named root tag, which is a compound, see
+//! [`read`] and [`write()`]. Whereas the network protocol of minecraft Java
+//! Edition since 1.20.2 uses a nameless root tag of any kind, see
+//! [`read_nameless`] and [`write_nameless`].
+//!
+//! See the documentation of [`Kind`](crate::kind::Kind) for the payloads. The
+//! encoding of numbers and length prefixes is selected by a [`Flavor`]:
+//!
+//! - [`Java`]: minecraft Java Edition, where all the numbers are big-endian,
+//!   and the strings are encoded in [Modified UTF-8](crate::mutf8).
+//! - [`Bedrock`]: minecraft Bedrock Edition files, where all the numbers are
+//!   little-endian.
+//! - [`BedrockNetwork`]: minecraft Bedrock Edition network protocol, which
+//!   uses VarInts for ints, longs and length prefixes.
+//!
+//! By default, an ill-formed string is an error. Wrap the flavor in [`Lossy`]
+//! to replace ill-formed sequences with `U+FFFD` instead.
+
+mod flavor;
+mod read;
+mod write;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::error::NbtError;
+
+pub use self::flavor::{Bedrock, BedrockNetwork, Flavor, Java, Lossy};
+pub(crate) use self::flavor::{take, take_array};
+pub use self::read::{read, read_nameless};
+#[cfg(feature = "std")]
+pub(crate) use self::write::write_payload;
+pub use self::write::{write, write_nameless};
+
+/// A byte sink that the encoders write into.
+///
+/// With the `std` feature, this is implemented for all `std::io::Write` types.
+/// Otherwise, this is implemented for [`Vec<u8>`].
+pub trait Write {
+    /// Writes all the bytes into the sink.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the underlying sink fails.
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), NbtError>;
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for W {
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), NbtError> {
+        Ok(std::io::Write::write_all(self, bytes)?)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Write for Vec<u8> {
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), NbtError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<W: Write + ?Sized> Write for &mut W {
+    #[inline]
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), NbtError> {
+        (**self).write_all(bytes)
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::binary::Flavor;
+use crate::binary::flavor::{take, take_array};
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::list::{is_wrapper, unwrap};
+use crate::value::{Compound, List, ThinSlice, Value};
+
+/// Reads a named root compound from the input, returning the root name and
+/// the compound.
+///
+/// The input is advanced past the root tag, so that concatenated tags can be
+/// read by calling this function repeatedly.
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed binary
+/// NBT, or the root tag is not a compound.
+pub fn read<F: Flavor>(input: &mut &[u8], flavor: F) -> Result<(String, Compound), NbtError> {
+    let mut decoder = Decoder {
+        input,
+        flavor,
+        depth: 0,
+    };
+    let kind = decoder.read_kind()?;
+    if kind != Some(Kind::Compound) {
+        return Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: kind,
+        });
+    }
+    let name = decoder.read_str()?.into_owned();
+    let compound = decoder.read_compound()?;
+    Ok((name, compound))
+}
+
+/// Reads a nameless root tag of any kind from the input.
+///
+/// This is the framing of minecraft Java Edition network protocol since 1.20.2
+/// (protocol 764), where the root tag ID is directly followed by the payload.
+/// The input is advanced past the root tag.
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed binary
+/// NBT, or the root tag is *TAG_End*.
+pub fn read_nameless<F: Flavor>(input: &mut &[u8], flavor: F) -> Result<Value, NbtError> {
+    let mut decoder = Decoder {
+        input,
+        flavor,
+        depth: 0,
+    };
+    match decoder.read_kind()? {
+        Some(kind) => decoder.read_value(kind),
+        None => Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: None,
+        }),
+    }
+}
+
+/// Decodes the payloads from a byte slice.
+struct Decoder<'a, 'de, F> {
+    input: &'a mut &'de [u8],
+    flavor: F,
+    depth: usize,
+}
+
+impl<'de, F: Flavor> Decoder<'_, 'de, F> {
+    /// Reads a tag ID, where [`None`] means *TAG_End*.
+    fn read_kind(&mut self) -> Result<Option<Kind>, NbtError> {
+        match take_array::<1>(self.input)? {
+            [0] => Ok(None),
+            [id] => Ok(Some(Kind::new(id)?)),
+        }
+    }
+
+    fn read_i8(&mut self) -> Result<i8, NbtError> {
+        Ok(i8::from_be_bytes(take_array(self.input)?))
+    }
+
+    fn read_str(&mut self) -> Result<Cow<'de, str>, NbtError> {
+        let len = self.flavor.read_str_len(self.input)?;
+        let bytes = take(self.input, len)?;
+        self.flavor.decode_str(bytes)
+    }
+
+    /// Reads `len` numbers by the given function.
+    fn read_array<T>(
+        &mut self,
+        len: usize,
+        read: fn(F, &mut &'de [u8]) -> Result<T, NbtError>,
+    ) -> Result<Vec<T>, NbtError> {
+        // Each number takes at least one byte, which bounds the capacity by
+        // the remaining input.
+        let mut vec = Vec::with_capacity(len.min(self.input.len()));
+        for _ in 0..len {
+            vec.push(read(self.flavor, self.input)?);
+        }
+        Ok(vec)
+    }
+
+    fn read_array_payload<T: Copy>(
+        &mut self,
+        read: fn(F, &mut &'de [u8]) -> Result<T, NbtError>,
+    ) -> Result<ThinSlice<T>, NbtError> {
+        let len = self.flavor.read_len(self.input)?;
+        Ok(ThinSlice::from(self.read_array(len, read)?))
+    }
+
+    fn read_value(&mut self, kind: Kind) -> Result<Value, NbtError> {
+        let flavor = self.flavor;
+        Ok(match kind {
+            Kind::Byte => Value::Byte(self.read_i8()?),
+            Kind::Short => Value::Short(flavor.read_i16(self.input)?),
+            Kind::Int => Value::Int(flavor.read_i32(self.input)?),
+            Kind::Long => Value::Long(flavor.read_i64(self.input)?),
+            Kind::Float => Value::Float(flavor.read_f32(self.input)?),
+            Kind::Double => Value::Double(flavor.read_f64(self.input)?),
+            Kind::ByteArray => Value::ByteArray(self.read_array_payload(read_i8)?),
+            Kind::String => Value::from(&*self.read_str()?),
+            Kind::List => Value::from(self.read_list()?),
+            Kind::Compound => Value::from(self.read_compound()?),
+            Kind::IntArray => Value::IntArray(self.read_array_payload(F::read_i32)?),
+            Kind::LongArray => Value::LongArray(self.read_array_payload(F::read_i64)?),
+        })
+    }
+
+    fn enter(&mut self) -> Result<(), NbtError> {
+        self.depth += 1;
+        if self.depth > NbtError::MAX_DEPTH {
+            return Err(NbtError::DepthLimit);
+        }
+        Ok(())
+    }
+
+    fn read_compound(&mut self) -> Result<Compound, NbtError> {
+        self.enter()?;
+        let mut compound = Compound::new();
+        while let Some(kind) = self.read_kind()? {
+            let name = self.read_str()?;
+            let value = self.read_value(kind)?;
+            compound.insert(&*name, value);
+        }
+        self.depth -= 1;
+        Ok(compound)
+    }
+
+    fn read_list(&mut self) -> Result<List, NbtError> {
+        self.enter()?;
+        let kind = self.read_kind()?;
+        let len = self.flavor.read_len(self.input)?;
+        let list = match kind {
+            None if len == 0 => List::End,
+            None => {
+                return Err(NbtError::UnexpectedKind {
+                    expected: Kind::List,
+                    found: None,
+                });
+            }
+            Some(Kind::Byte) => List::Byte(self.read_array(len, read_i8)?),
+            Some(Kind::Short) => List::Short(self.read_array(len, F::read_i16)?),
+            Some(Kind::Int) => List::Int(self.read_array(len, F::read_i32)?),
+            Some(Kind::Long) => List::Long(self.read_array(len, F::read_i64)?),
+            Some(Kind::Float) => List::Float(self.read_array(len, F::read_f32)?),
+            Some(Kind::Double) => List::Double(self.read_array(len, F::read_f64)?),
+            Some(Kind::Compound) => {
+                let mut vec = Vec::with_capacity(len.min(self.input.len()));
+                for _ in 0..len {
+                    vec.push(self.read_compound()?);
+                }
+                // The elements of a mixed list are unwrapped.
+                if vec.iter().any(is_wrapper) {
+                    List::Mixed(vec.into_iter().map(unwrap).collect())
+                } else {
+                    List::Compound(vec)
+                }
+            }
+            Some(kind) => {
+                let mut list = List::with_kind(kind);
+                for _ in 0..len {
+                    let value = self.read_value(kind)?;
+                    // The element is always of the list kind.
+                    let _ = list.push(value);
+                }
+                list
+            }
+        };
+        self.depth -= 1;
+        Ok(list)
+    }
+}
+
+fn read_i8<F>(_: F, input: &mut &[u8]) -> Result<i8, NbtError> {
+    Ok(i8::from_be_bytes(take_array(input)?))
+}
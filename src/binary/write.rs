@@ -0,0 +1,165 @@
+use crate::binary::{Flavor, Write};
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::list::is_wrapper;
+use crate::value::{Compound, List, Value};
+
+/// Writes a named root compound into the output.
+///
+/// # Errors
+///
+/// This function returns an error if the output fails, or a length is out of
+/// the range of its length prefix, e.g., a string is longer than 65,535 bytes.
+pub fn write<W: Write + ?Sized, F: Flavor>(
+    output: &mut W,
+    name: &str,
+    compound: &Compound,
+    flavor: F,
+) -> Result<(), NbtError> {
+    let mut encoder = Encoder { output, flavor };
+    encoder.write_kind(Some(Kind::Compound))?;
+    encoder.write_str(name)?;
+    encoder.write_compound(compound)
+}
+
+/// Writes a nameless root tag of any kind into the output.
+///
+/// This is the framing of minecraft Java Edition network protocol since 1.20.2
+/// (protocol 764), see [`read_nameless`](crate::binary::read_nameless).
+///
+/// # Errors
+///
+/// This function returns an error if the output fails, or a length is out of
+/// the range of its length prefix.
+pub fn write_nameless<W: Write + ?Sized, F: Flavor>(
+    output: &mut W,
+    value: &Value,
+    flavor: F,
+) -> Result<(), NbtError> {
+    let mut encoder = Encoder { output, flavor };
+    encoder.write_kind(Some(value.kind()))?;
+    encoder.write_value(value)
+}
+
+/// Writes the payload of the value into the output, without the tag ID.
+#[cfg(feature = "std")]
+pub(crate) fn write_payload<W: Write + ?Sized, F: Flavor>(
+    output: &mut W,
+    value: &Value,
+    flavor: F,
+) -> Result<(), NbtError> {
+    Encoder { output, flavor }.write_value(value)
+}
+
+/// Encodes the payloads into a byte sink.
+struct Encoder<'a, W: ?Sized, F> {
+    output: &'a mut W,
+    flavor: F,
+}
+
+impl<W: Write + ?Sized, F: Flavor> Encoder<'_, W, F> {
+    /// Writes a tag ID, where [`None`] means *TAG_End*.
+    fn write_kind(&mut self, kind: Option<Kind>) -> Result<(), NbtError> {
+        self.output.write_all(&[kind.map_or(0, |kind| kind as u8)])
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), NbtError> {
+        let bytes = self.flavor.encode_str(string);
+        self.flavor.write_str_len(self.output, bytes.len())?;
+        self.output.write_all(&bytes)
+    }
+
+    /// Writes the numbers by the given function, without the length prefix.
+    fn write_array<T: Copy>(
+        &mut self,
+        array: &[T],
+        write: fn(F, &mut W, T) -> Result<(), NbtError>,
+    ) -> Result<(), NbtError> {
+        for &element in array {
+            write(self.flavor, self.output, element)?;
+        }
+        Ok(())
+    }
+
+    fn write_array_payload<T: Copy>(
+        &mut self,
+        array: &[T],
+        write: fn(F, &mut W, T) -> Result<(), NbtError>,
+    ) -> Result<(), NbtError> {
+        self.flavor.write_len(self.output, array.len())?;
+        self.write_array(array, write)
+    }
+
+    fn write_value(&mut self, value: &Value) -> Result<(), NbtError> {
+        let flavor = self.flavor;
+        match value {
+            Value::Byte(value) => write_i8(flavor, self.output, *value),
+            Value::Short(value) => flavor.write_i16(self.output, *value),
+            Value::Int(value) => flavor.write_i32(self.output, *value),
+            Value::Long(value) => flavor.write_i64(self.output, *value),
+            Value::Float(value) => flavor.write_f32(self.output, *value),
+            Value::Double(value) => flavor.write_f64(self.output, *value),
+            Value::ByteArray(value) => self.write_array_payload(value, write_i8),
+            Value::String(value) => self.write_str(value),
+            Value::List(value) => self.write_list(value),
+            Value::Compound(value) => self.write_compound(value),
+            Value::IntArray(value) => self.write_array_payload(value, F::write_i32),
+            Value::LongArray(value) => self.write_array_payload(value, F::write_i64),
+        }
+    }
+
+    fn write_compound(&mut self, compound: &Compound) -> Result<(), NbtError> {
+        for (name, value) in compound {
+            self.write_kind(Some(value.kind()))?;
+            self.write_str(name)?;
+            self.write_value(value)?;
+        }
+        self.write_kind(None)
+    }
+
+    fn write_list(&mut self, list: &List) -> Result<(), NbtError> {
+        self.write_kind(list.kind())?;
+        self.flavor.write_len(self.output, list.len())?;
+        match list {
+            List::End => Ok(()),
+            List::Byte(vec) => self.write_array(vec, write_i8),
+            List::Short(vec) => self.write_array(vec, F::write_i16),
+            List::Int(vec) => self.write_array(vec, F::write_i32),
+            List::Long(vec) => self.write_array(vec, F::write_i64),
+            List::Float(vec) => self.write_array(vec, F::write_f32),
+            List::Double(vec) => self.write_array(vec, F::write_f64),
+            List::ByteArray(vec) => vec
+                .iter()
+                .try_for_each(|array| self.write_array_payload(array, write_i8)),
+            List::String(vec) => vec.iter().try_for_each(|string| self.write_str(string)),
+            List::List(vec) => vec.iter().try_for_each(|list| self.write_list(list)),
+            List::Compound(vec) => vec
+                .iter()
+                .try_for_each(|compound| self.write_compound(compound)),
+            List::IntArray(vec) => vec
+                .iter()
+                .try_for_each(|array| self.write_array_payload(array, F::write_i32)),
+            List::LongArray(vec) => vec
+                .iter()
+                .try_for_each(|array| self.write_array_payload(array, F::write_i64)),
+            List::Mixed(vec) => vec.iter().try_for_each(|value| self.write_element(value)),
+        }
+    }
+
+    /// Writes an element of a mixed list, wrapped in a compound if needed.
+    fn write_element(&mut self, value: &Value) -> Result<(), NbtError> {
+        match value {
+            Value::Compound(compound) if !is_wrapper(compound) => self.write_compound(compound),
+            value => {
+                self.write_kind(Some(value.kind()))?;
+                self.write_str("")?;
+                self.write_value(value)?;
+                self.write_kind(None)
+            }
+        }
+    }
+}
+
+fn write_i8<F, W: Write + ?Sized>(_: F, output: &mut W, value: i8) -> Result<(), NbtError> {
+    output.write_all(&value.to_be_bytes())
+}
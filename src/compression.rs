@@ -0,0 +1,173 @@
+//! This module provides transparent gzip and zlib compression of binary NBT,
+//! which requires the `compression` feature.
+//!
+//! Most NBT files on disk, e.g., `level.dat` and player data, are compressed
+//! by gzip, and the chunks in region files are compressed by zlib. When
+//! reading, the compression is detected by the magic bytes, see
+//! [`Compression::detect`].
+//!
+//! The decompressed data is limited to [`MAX_DECOMPRESSED_LEN`] bytes by
+//! default, so that a small but highly compressed input cannot exhaust the
+//! memory, see [`decompress_with_limit`] for other limits.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+use crate::binary::{self, Flavor};
+use crate::error::NbtError;
+use crate::value::Compound;
+
+/// The compression of binary NBT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// No compression.
+    Uncompressed,
+    /// Gzip compression, which is used by most NBT files.
+    #[default]
+    Gzip,
+    /// Zlib compression, which is used by the chunks in region files.
+    Zlib,
+}
+
+impl Compression {
+    /// Detects the compression of the bytes by the magic bytes.
+    ///
+    /// Gzip starts with `0x1F 0x8B`, and zlib starts with a two-byte header
+    /// `CMF FLG` where `CMF` is `0x78` and the header is a multiple of 31.
+    /// Since an uncompressed binary NBT starts with a tag ID, i.e., between
+    /// `0x00` and `0x0C`, the detection is unambiguous.
+    #[must_use]
+    pub fn detect(bytes: &[u8]) -> Self {
+        match *bytes {
+            [0x1F, 0x8B, ..] => Compression::Gzip,
+            [cmf @ 0x78, flg, ..] if (u16::from(cmf) << 8 | u16::from(flg)) % 31 == 0 => {
+                Compression::Zlib
+            }
+            _ => Compression::Uncompressed,
+        }
+    }
+}
+
+/// The default limit of the decompressed length, which is 100 MiB, the same
+/// as the quota of vanilla when reading NBT files.
+pub const MAX_DECOMPRESSED_LEN: usize = 100 << 20;
+
+/// Decompresses the bytes with the detected compression, borrowing them if
+/// not compressed.
+///
+/// See [`decompress_with_limit`] for more details.
+///
+/// # Errors
+///
+/// This function returns an error if the compressed data is corrupted, or the
+/// decompressed data is longer than [`MAX_DECOMPRESSED_LEN`].
+#[inline]
+pub fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, NbtError> {
+    decompress_with_limit(bytes, MAX_DECOMPRESSED_LEN)
+}
+
+/// Decompresses the bytes with the detected compression, borrowing them if
+/// not compressed, where the decompressed data is at most `limit` bytes.
+///
+/// The gzip data may consist of multiple members, which are decompressed
+/// into their concatenation, like `GZIPInputStream` of Java.
+///
+/// # Errors
+///
+/// This function returns an error if the compressed data is corrupted, or the
+/// decompressed data is longer than the limit.
+pub fn decompress_with_limit(bytes: &[u8], limit: usize) -> Result<Cow<'_, [u8]>, NbtError> {
+    let mut decompressed = Vec::new();
+    // One more byte is read to detect the data longer than the limit.
+    let take = (limit as u64).saturating_add(1);
+    match Compression::detect(bytes) {
+        Compression::Uncompressed => return Ok(Cow::Borrowed(bytes)),
+        Compression::Gzip => MultiGzDecoder::new(bytes)
+            .take(take)
+            .read_to_end(&mut decompressed)?,
+        Compression::Zlib => ZlibDecoder::new(bytes)
+            .take(take)
+            .read_to_end(&mut decompressed)?,
+    };
+    if decompressed.len() > limit {
+        return Err(NbtError::SizeLimit(limit));
+    }
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Compresses the bytes with the given compression, borrowing them if not
+/// compressed.
+///
+/// # Errors
+///
+/// This function never fails in practice, since it writes into memory.
+pub fn compress(bytes: &[u8], compression: Compression) -> Result<Cow<'_, [u8]>, NbtError> {
+    let level = flate2::Compression::default();
+    Ok(Cow::Owned(match compression {
+        Compression::Uncompressed => return Ok(Cow::Borrowed(bytes)),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), level);
+            io::Write::write_all(&mut encoder, bytes)?;
+            encoder.finish()?
+        }
+        Compression::Zlib => {
+            let mut encoder = ZlibEncoder::new(Vec::new(), level);
+            io::Write::write_all(&mut encoder, bytes)?;
+            encoder.finish()?
+        }
+    }))
+}
+
+/// Reads a named root compound from the reader, which may be compressed by
+/// gzip or zlib, or not compressed.
+///
+/// See [`binary::read`] for more details.
+///
+/// # Errors
+///
+/// This function returns an error if the reader fails, the compressed data is
+/// corrupted, the decompressed data is longer than [`MAX_DECOMPRESSED_LEN`],
+/// or it is not a well-formed binary NBT.
+pub fn read<R: Read, F: Flavor>(mut reader: R, flavor: F) -> Result<(String, Compound), NbtError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    binary::read(&mut &*decompress(&bytes)?, flavor)
+}
+
+/// Writes a named root compound into the writer with the given compression.
+///
+/// See [`binary::write()`] for more details.
+///
+/// # Errors
+///
+/// This function returns an error if the writer fails, or a length is out of
+/// the range of its length prefix.
+pub fn write<W: io::Write, F: Flavor>(
+    mut writer: W,
+    name: &str,
+    compound: &Compound,
+    flavor: F,
+    compression: Compression,
+) -> Result<(), NbtError> {
+    let level = flate2::Compression::default();
+    match compression {
+        Compression::Uncompressed => binary::write(&mut writer, name, compound, flavor),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(writer, level);
+            binary::write(&mut encoder, name, compound, flavor)?;
+            encoder.finish()?;
+            Ok(())
+        }
+        Compression::Zlib => {
+            let mut encoder = ZlibEncoder::new(writer, level);
+            binary::write(&mut encoder, name, compound, flavor)?;
+            encoder.finish()?;
+            Ok(())
+        }
+    }
+}
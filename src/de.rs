@@ -0,0 +1,485 @@
+//! This module provides the serde [`Deserializer`] that reads binary NBT
+//! directly, without building an intermediate value tree.
+//!
+//! Each tag is dispatched to the visitor by its kind:
+//!
+//! | NBT                              | Visitor                                 |
+//! | -------------------------------- | --------------------------------------- |
+//! | `Byte`, `Short`, `Int`, `Long`   | `visit_i8`, `visit_i16`, ... `visit_i64`|
+//! | `Float`, `Double`                | `visit_f32`, `visit_f64`                |
+//! | `String`                         | `visit_borrowed_str`, if possible       |
+//! | `List` and arrays                | `visit_seq`                             |
+//! | `Compound`                       | `visit_map`                             |
+//!
+//! In addition, `bool` is accepted from [`Kind::Byte`], bytes are borrowed
+//! from `ByteArray`, and an enum is accepted from a `String` of the variant
+//! name or a `Compound` containing only the variant, which are the forms
+//! written by the [serializer](crate::ser).
+
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::fmt::Display;
+
+use serde::de::value::{BorrowedStrDeserializer, StringDeserializer};
+use serde::de::{self, Deserialize, DeserializeSeed, IgnoredAny, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::binary::{Flavor, Java, take, take_array};
+use crate::error::NbtError;
+use crate::kind::Kind;
+
+/// Deserializes a value from a binary NBT of minecraft Java Edition, whose
+/// root is a named compound.
+///
+/// The root name and the bytes after the root tag are ignored.
+///
+/// # Errors
+///
+/// This function returns an error if the bytes are not a well-formed binary
+/// NBT, or do not match the structure of the value.
+pub fn from_slice<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, NbtError> {
+    T::deserialize(Deserializer::new(&mut &*bytes, Java))
+}
+
+/// Deserializes a value from a binary NBT of minecraft Java Edition read from
+/// the reader, whose root is a named compound.
+///
+/// The reader is read to the end, see [`from_slice`].
+///
+/// # Errors
+///
+/// This function returns an error if the reader fails, the bytes are not a
+/// well-formed binary NBT, or do not match the structure of the value.
+#[cfg(feature = "std")]
+pub fn from_reader<R, T>(mut reader: R) -> Result<T, NbtError>
+where
+    R: std::io::Read,
+    T: de::DeserializeOwned,
+{
+    let mut bytes = alloc::vec::Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// A serde deserializer that reads binary NBT from a byte slice.
+///
+/// By default, the root tag is a compound with a name, which is ignored.
+pub struct Deserializer<'a, 'de, F> {
+    input: &'a mut &'de [u8],
+    flavor: F,
+    depth: usize,
+    header: Header,
+}
+
+/// Tells what to read before the payload.
+enum Header {
+    /// The named root tag, which must be a compound.
+    Named,
+    /// The nameless root tag of any kind.
+    Nameless,
+    /// Nothing, since the header of a tag of the kind is already read.
+    Read(Kind),
+}
+
+impl<'a, 'de, F: Flavor> Deserializer<'a, 'de, F> {
+    /// Creates a new deserializer of a named root compound.
+    ///
+    /// The input is advanced past the root tag, so that concatenated tags can
+    /// be read by deserializing repeatedly.
+    #[inline]
+    pub fn new(input: &'a mut &'de [u8], flavor: F) -> Self {
+        Deserializer {
+            input,
+            flavor,
+            depth: 0,
+            header: Header::Named,
+        }
+    }
+
+    /// Makes the root tag nameless and of any kind, see
+    /// [`binary::read_nameless`](crate::binary::read_nameless).
+    #[inline]
+    #[must_use]
+    pub fn nameless(self) -> Self {
+        Deserializer {
+            header: Header::Nameless,
+            ..self
+        }
+    }
+
+    /// Creates a deserializer of a payload whose header is already read.
+    fn payload(&mut self, kind: Kind) -> Deserializer<'_, 'de, F> {
+        Deserializer {
+            input: self.input,
+            flavor: self.flavor,
+            depth: self.depth,
+            header: Header::Read(kind),
+        }
+    }
+
+    /// Reads the header if not yet, returning the kind of the tag.
+    fn begin(&mut self) -> Result<Kind, NbtError> {
+        match self.header {
+            Header::Named => match read_kind(self.input)? {
+                Some(Kind::Compound) => {
+                    read_str(self.input, self.flavor)?;
+                    Ok(Kind::Compound)
+                }
+                found => Err(NbtError::UnexpectedKind {
+                    expected: Kind::Compound,
+                    found,
+                }),
+            },
+            Header::Nameless => read_kind(self.input)?.ok_or(NbtError::UnexpectedKind {
+                expected: Kind::Compound,
+                found: None,
+            }),
+            Header::Read(kind) => Ok(kind),
+        }
+    }
+
+    fn enter(&self) -> Result<usize, NbtError> {
+        if self.depth >= NbtError::MAX_DEPTH {
+            return Err(NbtError::DepthLimit);
+        }
+        Ok(self.depth + 1)
+    }
+
+    /// Reads the header of a list, or the length prefix of an array, returning
+    /// the access to the elements.
+    fn seq_access(self, kind: Kind) -> Result<SeqAccess<'a, 'de, F>, NbtError> {
+        let depth = self.enter()?;
+        let (kind, len) = match kind {
+            Kind::ByteArray => (Some(Kind::Byte), self.flavor.read_len(self.input)?),
+            Kind::IntArray => (Some(Kind::Int), self.flavor.read_len(self.input)?),
+            Kind::LongArray => (Some(Kind::Long), self.flavor.read_len(self.input)?),
+            _ => (read_kind(self.input)?, self.flavor.read_len(self.input)?),
+        };
+        if kind.is_none() && len > 0 {
+            return Err(NbtError::UnexpectedKind {
+                expected: Kind::List,
+                found: None,
+            });
+        }
+        Ok(SeqAccess {
+            input: self.input,
+            flavor: self.flavor,
+            depth,
+            kind,
+            len,
+        })
+    }
+
+    fn map_access(self) -> Result<MapAccess<'a, 'de, F>, NbtError> {
+        Ok(MapAccess {
+            depth: self.enter()?,
+            input: self.input,
+            flavor: self.flavor,
+            kind: None,
+            done: false,
+        })
+    }
+}
+
+impl<'de, F: Flavor> de::Deserializer<'de> for Deserializer<'_, 'de, F> {
+    type Error = NbtError;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, NbtError> {
+        let kind = self.begin()?;
+        let flavor = self.flavor;
+        match kind {
+            Kind::Byte => visitor.visit_i8(i8::from_be_bytes(take_array(self.input)?)),
+            Kind::Short => visitor.visit_i16(flavor.read_i16(self.input)?),
+            Kind::Int => visitor.visit_i32(flavor.read_i32(self.input)?),
+            Kind::Long => visitor.visit_i64(flavor.read_i64(self.input)?),
+            Kind::Float => visitor.visit_f32(flavor.read_f32(self.input)?),
+            Kind::Double => visitor.visit_f64(flavor.read_f64(self.input)?),
+            Kind::String => match read_str(self.input, flavor)? {
+                Cow::Borrowed(string) => visitor.visit_borrowed_str(string),
+                Cow::Owned(string) => visitor.visit_string(string),
+            },
+            Kind::Compound => {
+                let mut access = self.map_access()?;
+                let value = visitor.visit_map(&mut access)?;
+                access.end()?;
+                Ok(value)
+            }
+            Kind::ByteArray | Kind::List | Kind::IntArray | Kind::LongArray => {
+                let mut access = self.seq_access(kind)?;
+                let value = visitor.visit_seq(&mut access)?;
+                access.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.begin()? {
+            Kind::Byte => visitor.visit_bool(take_array::<1>(self.input)? != [0]),
+            kind => self.payload(kind).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, NbtError> {
+        match self.begin()? {
+            Kind::ByteArray => {
+                let len = self.flavor.read_len(self.input)?;
+                visitor.visit_borrowed_bytes(take(self.input, len)?)
+            }
+            kind => self.payload(kind).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        // A missing entry of a compound is handled by the visitor of the
+        // compound, so a present tag is always some.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NbtError> {
+        self.deserialize_any(IgnoredAny)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, NbtError> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, NbtError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, NbtError> {
+        match self.begin()? {
+            Kind::String => match read_str(self.input, self.flavor)? {
+                Cow::Borrowed(string) => {
+                    visitor.visit_enum(BorrowedStrDeserializer::<NbtError>::new(string))
+                }
+                Cow::Owned(string) => {
+                    visitor.visit_enum(StringDeserializer::<NbtError>::new(string))
+                }
+            },
+            Kind::Compound => {
+                let depth = self.enter()?;
+                let Some(kind) = read_kind(self.input)? else {
+                    return Err(de::Error::invalid_length(0, &"a compound of one variant"));
+                };
+                let variant = read_str(self.input, self.flavor)?;
+                let value = visitor.visit_enum(EnumAccess {
+                    variant,
+                    payload: Deserializer {
+                        input: &mut *self.input,
+                        flavor: self.flavor,
+                        depth,
+                        header: Header::Read(kind),
+                    },
+                })?;
+                match read_kind(self.input)? {
+                    None => Ok(value),
+                    Some(_) => Err(de::Error::invalid_length(2, &"a compound of one variant")),
+                }
+            }
+            found => Err(NbtError::UnexpectedKind {
+                expected: Kind::Compound,
+                found: Some(found),
+            }),
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Accesses the elements of a list or an array.
+struct SeqAccess<'a, 'de, F> {
+    input: &'a mut &'de [u8],
+    flavor: F,
+    depth: usize,
+    kind: Option<Kind>,
+    len: usize,
+}
+
+impl<F> SeqAccess<'_, '_, F> {
+    /// Checks that all the elements are consumed by the visitor.
+    fn end(self) -> Result<(), NbtError> {
+        match self.len {
+            0 => Ok(()),
+            len => Err(de::Error::custom(format_args!(
+                "{len} trailing elements of the list"
+            ))),
+        }
+    }
+}
+
+impl<'de, F: Flavor> de::SeqAccess<'de> for SeqAccess<'_, 'de, F> {
+    type Error = NbtError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, NbtError> {
+        // A non-empty list always has a kind.
+        let Some(kind) = self.kind.filter(|_| self.len > 0) else {
+            return Ok(None);
+        };
+        self.len -= 1;
+        seed.deserialize(Deserializer {
+            input: &mut *self.input,
+            flavor: self.flavor,
+            depth: self.depth,
+            header: Header::Read(kind),
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+/// Accesses the entries of a compound.
+struct MapAccess<'a, 'de, F> {
+    input: &'a mut &'de [u8],
+    flavor: F,
+    depth: usize,
+    /// The kind of the value whose key is just read.
+    kind: Option<Kind>,
+    done: bool,
+}
+
+impl<F: Flavor> MapAccess<'_, '_, F> {
+    /// Skips the entries not consumed by the visitor.
+    fn end(&mut self) -> Result<(), NbtError> {
+        while de::MapAccess::next_entry::<IgnoredAny, IgnoredAny>(self)?.is_some() {}
+        Ok(())
+    }
+}
+
+impl<'de, F: Flavor> de::MapAccess<'de> for MapAccess<'_, 'de, F> {
+    type Error = NbtError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, NbtError> {
+        if self.done {
+            return Ok(None);
+        }
+        let Some(kind) = read_kind(self.input)? else {
+            self.done = true;
+            return Ok(None);
+        };
+        self.kind = Some(kind);
+        match read_str(self.input, self.flavor)? {
+            Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::<NbtError>::new(key)),
+            Cow::Owned(key) => seed.deserialize(StringDeserializer::<NbtError>::new(key)),
+        }
+        .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, NbtError> {
+        let Some(kind) = self.kind.take() else {
+            return Err(de::Error::custom(
+                "value of compound requested before its key",
+            ));
+        };
+        seed.deserialize(Deserializer {
+            input: &mut *self.input,
+            flavor: self.flavor,
+            depth: self.depth,
+            header: Header::Read(kind),
+        })
+    }
+}
+
+/// Accesses the variant of an enum, which is the only entry of a compound.
+struct EnumAccess<'a, 'de, F> {
+    variant: Cow<'de, str>,
+    payload: Deserializer<'a, 'de, F>,
+}
+
+impl<'a, 'de, F: Flavor> de::EnumAccess<'de> for EnumAccess<'a, 'de, F> {
+    type Error = NbtError;
+    type Variant = Deserializer<'a, 'de, F>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), NbtError> {
+        let variant = match self.variant {
+            Cow::Borrowed(variant) => {
+                seed.deserialize(BorrowedStrDeserializer::<NbtError>::new(variant))?
+            }
+            Cow::Owned(variant) => {
+                seed.deserialize(StringDeserializer::<NbtError>::new(variant))?
+            }
+        };
+        Ok((variant, self.payload))
+    }
+}
+
+impl<'de, F: Flavor> de::VariantAccess<'de> for Deserializer<'_, 'de, F> {
+    type Error = NbtError;
+
+    fn unit_variant(self) -> Result<(), NbtError> {
+        de::Deserializer::deserialize_any(self, IgnoredAny).map(drop)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, NbtError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, NbtError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, NbtError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+/// Reads a tag ID, where [`None`] means *TAG_End*.
+fn read_kind(input: &mut &[u8]) -> Result<Option<Kind>, NbtError> {
+    match take_array::<1>(input)? {
+        [0] => Ok(None),
+        [id] => Ok(Some(Kind::new(id)?)),
+    }
+}
+
+fn read_str<'de, F: Flavor>(input: &mut &'de [u8], flavor: F) -> Result<Cow<'de, str>, NbtError> {
+    let len = flavor.read_str_len(input)?;
+    flavor.decode_str(take(input, len)?)
+}
+
+impl de::Error for NbtError {
+    fn custom<T: Display>(message: T) -> Self {
+        NbtError::Message(message.to_string())
+    }
+}
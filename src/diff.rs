@@ -0,0 +1,563 @@
+//! This module provides [`diff`], which compares two trees structurally, and
+//! the [`Patch`] it returns, which lists the changes by [`NbtPath`] and can be
+//! applied back.
+//!
+//! ```
+//! use znbt::diff::{self, Change};
+//! use znbt::value::{Compound, Value};
+//!
+//! let old: Compound = r#"{id: "minecraft:stone", count: 1, tag: {a: 1}}"#.parse()?;
+//! let new: Compound = r#"{id: "minecraft:stone", count: 1L, tag: {b: 2}}"#.parse()?;
+//! let patch = diff::diff(&old, &new);
+//! assert_eq!(patch.to_string(), "~ count: 1 -> 1L\n- tag.a: 1\n+ tag.b: 2\n");
+//! assert!(matches!(&patch.changes()[0], Change::Replace { old: Value::Int(1), .. }));
+//!
+//! let mut tree = old.clone();
+//! patch.apply(&mut tree)?;
+//! assert_eq!(tree, new);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Format
+//!
+//! A patch is converted to NBT by [`Patch::to_compound`], where each change
+//! is a compound of the operation `op`, the `path` as a string, and the
+//! values as they are:
+//!
+//! ```text
+//! {changes: [{op: "replace", path: "count", old: 1, new: 1L}, ...]}
+//! ```
+//!
+//! Under serde, a patch is a sequence of the same changes, where the values
+//! are SNBT strings, so that the kinds are kept in formats like JSON:
+//!
+//! ```text
+//! [{"op": "replace", "path": "count", "old": "1", "new": "1L"}, ...]
+//! ```
+//!
+//! The SNBT strings are parsed by the 1.21.5 [`Dialect`], which reads back
+//! the [`List::Mixed`] printed with its elements unwrapped. A value that
+//! contains a non-finite float cannot be serialized, since SNBT has no
+//! syntax for it.
+//!
+//! [`Dialect`]: crate::snbt::Dialect
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+use core::mem;
+
+use crate::path::{NbtPath, Node, Target, select_mut};
+use crate::value::{Compound, List, Value, ValueRef};
+
+/// Compares two trees, returning the changes from the old to the new one.
+///
+/// The entries of compounds are compared by key regardless of their order,
+/// where the removed and replaced entries are listed in the order of the old
+/// compound, followed by the added entries in the order of the new compound.
+/// Two lists of the same element kind are compared by index, where the
+/// elements beyond the shorter list are added in order or removed from the
+/// back. Any other pair of different values, including arrays and values of
+/// different kinds, is listed as a [`Change::Replace`]. The floats are
+/// compared bitwise, so that a NaN is the same as itself.
+#[must_use]
+pub fn diff(old: &Compound, new: &Compound) -> Patch {
+    let mut differ = Differ {
+        nodes: Vec::new(),
+        changes: Vec::new(),
+    };
+    differ.compound(old, new);
+    Patch {
+        changes: differ.changes,
+    }
+}
+
+/// A change of the value at a path, see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "lowercase"))]
+pub enum Change {
+    /// The value is added as an entry of a compound, or an element at the end
+    /// of a list.
+    Add {
+        /// The path of the value.
+        #[cfg_attr(feature = "serde", serde(with = "path_text"))]
+        path: NbtPath,
+        /// The added value.
+        #[cfg_attr(feature = "serde", serde(with = "value_text"))]
+        value: Value,
+    },
+    /// The value is removed from a compound or list.
+    Remove {
+        /// The path of the value.
+        #[cfg_attr(feature = "serde", serde(with = "path_text"))]
+        path: NbtPath,
+        /// The removed value.
+        #[cfg_attr(feature = "serde", serde(with = "value_text"))]
+        value: Value,
+    },
+    /// The value is replaced by another value, possibly of another kind.
+    Replace {
+        /// The path of the value.
+        #[cfg_attr(feature = "serde", serde(with = "path_text"))]
+        path: NbtPath,
+        /// The value before the change.
+        #[cfg_attr(feature = "serde", serde(with = "value_text"))]
+        old: Value,
+        /// The value after the change.
+        #[cfg_attr(feature = "serde", serde(with = "value_text"))]
+        new: Value,
+    },
+}
+
+impl Change {
+    /// Returns the path of the changed value.
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &NbtPath {
+        match self {
+            Change::Add { path, .. }
+            | Change::Remove { path, .. }
+            | Change::Replace { path, .. } => path,
+        }
+    }
+
+    /// Returns `true` if the change replaces a value by a value of another
+    /// kind, e.g., an [`Int`](Value::Int) by a [`Long`](Value::Long).
+    #[inline]
+    #[must_use]
+    pub fn is_kind_change(&self) -> bool {
+        match self {
+            Change::Replace { old, new, .. } => old.kind() != new.kind(),
+            _ => false,
+        }
+    }
+
+    /// Applies the change, where the value at the path must be as described.
+    fn apply(&self, root: &mut Compound) -> Result<(), &'static str> {
+        let Some((last, nodes)) = self.path().nodes().split_last() else {
+            return Err("empty path");
+        };
+        let mut target = Target::Compound(root);
+        for node in nodes {
+            match node {
+                Node::Key(_) => {}
+                Node::Index(index) if *index >= 0 => {}
+                _ => return Err("unsupported path"),
+            }
+            let mut selected = Vec::with_capacity(1);
+            select_mut(node, target, None, &mut selected);
+            target = selected.pop().ok_or("missing parent")?;
+        }
+        match (last, target) {
+            (Node::Key(key), Target::Compound(compound)) => match self {
+                Change::Add { value, .. } if !compound.contains_key(key) => {
+                    compound.insert(key.as_str(), value.clone());
+                }
+                Change::Remove { value, .. } if same_entry(compound.get(key), value) => {
+                    compound.remove(key);
+                }
+                Change::Replace { old, new, .. } if same_entry(compound.get(key), old) => {
+                    compound.insert(key.as_str(), new.clone());
+                }
+                _ => return Err("value mismatch"),
+            },
+            (&Node::Index(index), Target::List(list)) => {
+                let index = usize::try_from(index).map_err(|_| "unsupported path")?;
+                let element = list.get(index);
+                match self {
+                    Change::Add { value, .. } if index == list.len() => {
+                        list.push(value.clone()).map_err(|_| "kind mismatch")?;
+                    }
+                    Change::Remove { value, .. }
+                        if element.is_some_and(|element| same(element, value.as_borrowed())) =>
+                    {
+                        list.remove(index);
+                    }
+                    Change::Replace { old, new, .. }
+                        if element.is_some_and(|element| same(element, old.as_borrowed())) =>
+                    {
+                        list.set(index, new.clone()).map_err(|_| "kind mismatch")?;
+                    }
+                    _ => return Err("value mismatch"),
+                }
+            }
+            (Node::Key(_), _) => return Err("expected compound"),
+            (Node::Index(_), _) => return Err("expected list"),
+            _ => return Err("unsupported path"),
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the operation of the change.
+    fn op(&self) -> &'static str {
+        match self {
+            Change::Add { .. } => "add",
+            Change::Remove { .. } => "remove",
+            Change::Replace { .. } => "replace",
+        }
+    }
+
+    /// Converts the change to a compound, see the [module documentation](self).
+    fn to_compound(&self) -> Compound {
+        let mut compound = Compound::with_capacity(4);
+        compound.insert("op", self.op());
+        compound.insert("path", self.path().to_string());
+        match self {
+            Change::Add { value, .. } | Change::Remove { value, .. } => {
+                compound.insert("value", value.clone());
+            }
+            Change::Replace { old, new, .. } => {
+                compound.insert("old", old.clone());
+                compound.insert("new", new.clone());
+            }
+        }
+        compound
+    }
+
+    /// Converts a compound back to a change.
+    fn from_compound(compound: &Compound) -> Result<Self, &'static str> {
+        let get = |key| compound.get(key).cloned().ok_or("missing value");
+        let path = match compound.get("path") {
+            Some(Value::String(path)) => path.parse().map_err(|_| "invalid path")?,
+            _ => return Err("expected path"),
+        };
+        match compound.get("op") {
+            Some(Value::String(op)) => match op.as_str() {
+                "add" => Ok(Change::Add {
+                    path,
+                    value: get("value")?,
+                }),
+                "remove" => Ok(Change::Remove {
+                    path,
+                    value: get("value")?,
+                }),
+                "replace" => Ok(Change::Replace {
+                    path,
+                    old: get("old")?,
+                    new: get("new")?,
+                }),
+                _ => Err("unknown op"),
+            },
+            _ => Err("expected op"),
+        }
+    }
+}
+
+impl Display for Change {
+    /// Writes the change in one line, e.g., `~ count: 1 -> 1L`, where the
+    /// operation is `+`, `-` or `~`, and the values are compact SNBT, which
+    /// fails if a value contains a non-finite float.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Add { path, value } => write!(f, "+ {path}: {value}"),
+            Change::Remove { path, value } => write!(f, "- {path}: {value}"),
+            Change::Replace { path, old, new } => write!(f, "~ {path}: {old} -> {new}"),
+        }
+    }
+}
+
+/// The list of changes from one tree to another, see the
+/// [module documentation](self).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Patch {
+    changes: Vec<Change>,
+}
+
+impl Patch {
+    /// Creates a new empty patch.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Patch {
+            changes: Vec::new(),
+        }
+    }
+
+    /// Returns the number of changes in the patch.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if the patch contains no changes, i.e., the trees are
+    /// equal.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the changes in the order they are applied.
+    #[inline]
+    #[must_use]
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Appends a change to the patch.
+    #[inline]
+    pub fn push(&mut self, change: Change) {
+        self.changes.push(change);
+    }
+
+    /// Applies the changes in order to the tree.
+    ///
+    /// Each change is checked against the tree, i.e., a removed or replaced
+    /// value must be the same as the old value, where the floats are compared
+    /// bitwise, an added entry must be missing,
+    /// and an added element must be at the end of its list. The paths must
+    /// only consist of keys and non-negative indices.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a change does not apply to the tree,
+    /// in which case the tree is left unchanged.
+    pub fn apply(&self, root: &mut Compound) -> Result<(), PatchError> {
+        let mut tree = root.clone();
+        for (index, change) in self.changes.iter().enumerate() {
+            change
+                .apply(&mut tree)
+                .map_err(|message| PatchError { index, message })?;
+        }
+        *root = tree;
+        Ok(())
+    }
+
+    /// Converts the patch to a compound, see the [module documentation](self).
+    #[must_use]
+    pub fn to_compound(&self) -> Compound {
+        let changes = self.changes.iter().map(Change::to_compound).collect();
+        Compound::from_iter([("changes", List::Compound(changes))])
+    }
+
+    /// Converts a compound created by [`Patch::to_compound`] back to a patch.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the compound is not a well-formed
+    /// patch.
+    pub fn from_compound(compound: &Compound) -> Result<Self, PatchError> {
+        let error = |index, message| PatchError { index, message };
+        let changes = match compound.get("changes") {
+            Some(Value::List(list)) => match &**list {
+                List::End => &[][..],
+                List::Compound(changes) => changes,
+                _ => return Err(error(0, "expected compound")),
+            },
+            _ => return Err(error(0, "expected changes")),
+        };
+        let changes = changes
+            .iter()
+            .enumerate()
+            .map(|(index, change)| Change::from_compound(change).map_err(|e| error(index, e)))
+            .collect::<Result<_, _>>()?;
+        Ok(Patch { changes })
+    }
+}
+
+impl Display for Patch {
+    /// Writes each change in a line, see [`Change`].
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> IntoIterator for &'a Patch {
+    type Item = &'a Change;
+    type IntoIter = core::slice::Iter<'a, Change>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.iter()
+    }
+}
+
+impl FromIterator<Change> for Patch {
+    fn from_iter<I: IntoIterator<Item = Change>>(iter: I) -> Self {
+        Patch {
+            changes: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// An error that is returned when a patch cannot be applied or converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchError {
+    index: usize,
+    message: &'static str,
+}
+
+impl PatchError {
+    /// Returns the index of the change where the error occurs.
+    #[inline]
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at change {}", self.message, self.index)
+    }
+}
+
+impl Error for PatchError {}
+
+/// The state of [`diff`], i.e., the path of the compared values.
+struct Differ {
+    nodes: Vec<Node>,
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn compound(&mut self, old: &Compound, new: &Compound) {
+        for (key, value) in old {
+            self.nodes.push(Node::Key(String::from(key)));
+            match new.get(key) {
+                Some(other) => self.value(value.as_borrowed(), other.as_borrowed()),
+                None => self.push(|path| Change::Remove {
+                    path,
+                    value: value.clone(),
+                }),
+            }
+            self.nodes.pop();
+        }
+        for (key, value) in new.iter().filter(|(key, _)| !old.contains_key(key)) {
+            self.nodes.push(Node::Key(String::from(key)));
+            self.push(|path| Change::Add {
+                path,
+                value: value.clone(),
+            });
+            self.nodes.pop();
+        }
+    }
+
+    /// Compares two lists of the same element kind, whose lengths are in the
+    /// range of `i32`.
+    fn list(&mut self, old: &List, new: &List) {
+        for (index, (old, new)) in old.iter().zip(new.iter()).enumerate() {
+            self.nodes.push(Node::Index(index as i32));
+            self.value(old, new);
+            self.nodes.pop();
+        }
+        for (index, value) in new.iter().enumerate().skip(old.len()) {
+            self.nodes.push(Node::Index(index as i32));
+            self.push(|path| Change::Add {
+                path,
+                value: value.to_owned(),
+            });
+            self.nodes.pop();
+        }
+        for (index, value) in old.iter().enumerate().skip(new.len()).rev() {
+            self.nodes.push(Node::Index(index as i32));
+            self.push(|path| Change::Remove {
+                path,
+                value: value.to_owned(),
+            });
+            self.nodes.pop();
+        }
+    }
+
+    fn value(&mut self, old: ValueRef<'_>, new: ValueRef<'_>) {
+        match (old, new) {
+            (ValueRef::Compound(old), ValueRef::Compound(new)) => self.compound(old, new),
+            (ValueRef::List(old), ValueRef::List(new))
+                if mem::discriminant(old) == mem::discriminant(new)
+                    && old.len().max(new.len()) <= i32::MAX as usize =>
+            {
+                self.list(old, new);
+            }
+            _ if same(old, new) => {}
+            _ => self.push(|path| Change::Replace {
+                path,
+                old: old.to_owned(),
+                new: new.to_owned(),
+            }),
+        }
+    }
+
+    /// Pushes a change of the value at the current path.
+    fn push(&mut self, change: impl FnOnce(NbtPath) -> Change) {
+        let path = NbtPath::from(self.nodes.clone());
+        self.changes.push(change(path));
+    }
+}
+
+/// Returns `true` if the values are the same, where the floats are compared
+/// bitwise, so that a NaN is the same as itself, and the entries of compounds
+/// are compared regardless of their order.
+fn same(a: ValueRef<'_>, b: ValueRef<'_>) -> bool {
+    match (a, b) {
+        (ValueRef::Float(a), ValueRef::Float(b)) => a.to_bits() == b.to_bits(),
+        (ValueRef::Double(a), ValueRef::Double(b)) => a.to_bits() == b.to_bits(),
+        (ValueRef::List(a), ValueRef::List(b)) => {
+            mem::discriminant(a) == mem::discriminant(b)
+                && a.len() == b.len()
+                && a.iter().zip(b.iter()).all(|(a, b)| same(a, b))
+        }
+        (ValueRef::Compound(a), ValueRef::Compound(b)) => {
+            a.len() == b.len() && a.iter().all(|(key, a)| same_entry(b.get(key), a))
+        }
+        _ => a == b,
+    }
+}
+
+/// Returns `true` if the entry is present and the same as the value.
+fn same_entry(entry: Option<&Value>, value: &Value) -> bool {
+    entry.is_some_and(|entry| same(entry.as_borrowed(), value.as_borrowed()))
+}
+
+/// The serde helper that represents a path by its string.
+#[cfg(feature = "serde")]
+mod path_text {
+    use alloc::string::String;
+
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::ser::Serializer;
+
+    use crate::path::NbtPath;
+
+    pub fn serialize<S: Serializer>(path: &NbtPath, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(path)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NbtPath, D::Error> {
+        NbtPath::parse(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// The serde helper that represents a value by its SNBT string, which is
+/// parsed by the 1.21.5 dialect to read back [`List::Mixed`].
+#[cfg(feature = "serde")]
+mod value_text {
+    use alloc::string::String;
+
+    use serde::de::{self, Deserialize, Deserializer};
+    use serde::ser::{self, Serializer};
+
+    use crate::snbt::{self, Dialect, Printer};
+    use crate::value::Value;
+
+    /// Serializes the value, rejecting the non-finite floats, which SNBT has
+    /// no syntax for.
+    pub fn serialize<S: Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+        let string = Printer::compact()
+            .to_string(value.as_borrowed())
+            .map_err(|_| ser::Error::custom("non-finite float in SNBT"))?;
+        serializer.serialize_str(&string)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        snbt::parse_with(&String::deserialize(deserializer)?, Dialect::V1_21_5)
+            .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,114 @@
+//! This module provides the error type [`NbtError`] shared by the encoders and
+//! decoders of this crate.
+
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+
+use crate::kind::{Kind, NbtKindError};
+
+/// An error that is returned when cannot encode or decode NBT data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum NbtError {
+    /// The tag ID is not one of the twelve [`Kind`]s.
+    Kind(NbtKindError),
+    /// The input ends before a complete tag is decoded.
+    UnexpectedEof,
+    /// The tag is not of the expected kind, where [`None`] means *TAG_End*.
+    UnexpectedKind {
+        /// The expected kind.
+        expected: Kind,
+        /// The actual kind.
+        found: Option<Kind>,
+    },
+    /// The length prefix of an array, list or string is negative.
+    NegativeLength(i32),
+    /// The string is not well-formed.
+    InvalidString,
+    /// The VarInt is longer than its maximum length, see
+    /// [`BedrockNetwork`](crate::binary::BedrockNetwork).
+    InvalidVarInt,
+    /// The length of an array, list or string exceeds the range of its length
+    /// prefix.
+    LengthOverflow(usize),
+    /// The tags are nested too deep, see [`NbtError::MAX_DEPTH`].
+    DepthLimit,
+    /// The decompressed data is longer than the limit, see
+    /// [`decompress_with_limit`](crate::compression::decompress_with_limit).
+    #[cfg(feature = "compression")]
+    SizeLimit(usize),
+    /// The Rust type cannot be represented in NBT, e.g., `i128`.
+    Unsupported(&'static str),
+    /// The methods of a streaming [`Writer`](crate::stream::Writer) are
+    /// called out of order, e.g., an entry is written outside of a compound.
+    #[cfg(feature = "std")]
+    InvalidState(&'static str),
+    /// A custom error message, e.g., from serde.
+    Message(String),
+    /// An I/O error occurred.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl NbtError {
+    /// The maximum nesting depth of compounds and lists, which is the same as
+    /// the limit of minecraft.
+    pub const MAX_DEPTH: usize = 512;
+}
+
+impl Display for NbtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtError::Kind(error) => Display::fmt(error, f),
+            NbtError::UnexpectedEof => f.write_str("unexpected end of input"),
+            NbtError::UnexpectedKind {
+                expected,
+                found: Some(found),
+            } => write!(f, "expected tag `{expected:?}`, found `{found:?}`"),
+            NbtError::UnexpectedKind {
+                expected,
+                found: None,
+            } => write!(f, "expected tag `{expected:?}`, found `End`"),
+            NbtError::NegativeLength(len) => write!(f, "negative length `{len}`"),
+            NbtError::InvalidString => f.write_str("invalid string"),
+            NbtError::InvalidVarInt => f.write_str("invalid VarInt"),
+            NbtError::LengthOverflow(len) => write!(f, "length `{len}` out of range"),
+            NbtError::DepthLimit => write!(f, "nested deeper than {}", NbtError::MAX_DEPTH),
+            #[cfg(feature = "compression")]
+            NbtError::SizeLimit(limit) => write!(f, "decompressed data longer than {limit} bytes"),
+            NbtError::Unsupported(what) => write!(f, "{what} is not supported in NBT"),
+            #[cfg(feature = "std")]
+            NbtError::InvalidState(what) => write!(f, "invalid writer state: {what}"),
+            NbtError::Message(message) => f.write_str(message),
+            #[cfg(feature = "std")]
+            NbtError::Io(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for NbtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NbtError::Kind(error) => Some(error),
+            #[cfg(feature = "std")]
+            NbtError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<NbtKindError> for NbtError {
+    #[inline]
+    fn from(error: NbtKindError) -> Self {
+        NbtError::Kind(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for NbtError {
+    #[inline]
+    fn from(error: std::io::Error) -> Self {
+        NbtError::Io(error)
+    }
+}
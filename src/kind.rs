@@ -12,6 +12,9 @@ use core::{fmt, mem};
 /// This does not include *TAG_End* which is used to mark the end of compound
 /// tags, since it does not represent any NBT value. To represent the marker
 /// tag, use [`None`] of an [`Option<Kind>`].
+///
+/// Under serde, this is represented by the variant name, e.g., `"Byte"`. To
+/// represent it by the numeric type ID instead, see [`id`].
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -50,7 +53,8 @@ pub enum Kind {
     /// A UTF-8 string, which has a size rather than being null-terminated.
     ///
     /// The payloads consist of an unsigned 16-bit integer `L`, then a UTF-8
-    /// string resembled by `L` bytes.
+    /// string resembled by `L` bytes. Note that Java Edition actually uses
+    /// [Modified UTF-8](crate::mutf8).
     #[doc(alias = "TAG_String")]
     String = 0x08,
     /// An ordered list of tags of the same type.
@@ -144,3 +148,49 @@ impl Display for NbtKindError {
 }
 
 impl Error for NbtKindError {}
+
+/// The serde helper that represents [`Kind`] by its numeric type ID, to be
+/// used with `#[serde(with = "znbt::kind::id")]`.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use znbt::kind::Kind;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Schema {
+///     #[serde(with = "znbt::kind::id")]
+///     kind: Kind,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod id {
+    use serde::de::{self, Deserialize, Deserializer, Unexpected};
+    use serde::ser::Serializer;
+
+    use super::Kind;
+
+    /// Serializes the kind as its numeric type ID, i.e., an `u8`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the serializer fails.
+    pub fn serialize<S: Serializer>(kind: &Kind, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*kind as u8)
+    }
+
+    /// Deserializes the kind from its numeric type ID, see [`Kind::new`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the deserializer fails, or the ID is
+    /// not between `1` and `12`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Kind, D::Error> {
+        let id = u8::deserialize(deserializer)?;
+        Kind::new(id).map_err(|_| {
+            de::Error::invalid_value(
+                Unexpected::Unsigned(u64::from(id)),
+                &"a tag ID from 1 to 12",
+            )
+        })
+    }
+}
@@ -5,7 +5,32 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
-#[cfg(not(feature = "std"))]
 extern crate alloc;
 
+#[cfg(feature = "serde")]
+pub mod array;
+pub mod binary;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod diff;
+pub mod error;
 pub mod kind;
+pub mod mutf8;
+pub mod path;
+#[cfg(feature = "serde")]
+pub mod ser;
+pub mod snbt;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod value;
+pub mod view;
+pub mod visit;
+
+#[cfg(all(feature = "serde", feature = "std"))]
+pub use self::de::from_reader;
+#[cfg(feature = "serde")]
+pub use self::de::from_slice;
+#[cfg(feature = "serde")]
+pub use self::ser::{to_vec, to_writer};
@@ -0,0 +1,209 @@
+//! This module provides the decoder and encoder of *Modified UTF-8* (MUTF-8),
+//! which is the string encoding of minecraft Java Edition.
+//!
+//! MUTF-8 differs from UTF-8 in two ways:
+//!
+//! - The null character `U+0000` is encoded as two bytes `0xC0 0x80`, so that
+//!   the encoded string never contains a zero byte.
+//! - A supplementary character (above `U+FFFF`) is encoded as a surrogate pair,
+//!   each of which is encoded as three bytes, like CESU-8.
+//!
+//! Since most strings contain neither of them, both the decoder and encoder
+//! borrow the input if it is already valid in the other encoding.
+//!
+//! See [`DataInput`] of Java for more details.
+//!
+//! [`DataInput`]: https://docs.oracle.com/javase/8/docs/api/java/io/DataInput.html#modified-utf-8
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char::REPLACEMENT_CHARACTER;
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+use core::str;
+
+/// Decodes MUTF-8 bytes into a string.
+///
+/// The bytes are borrowed if they are valid UTF-8, which is the case unless
+/// the string contains null or supplementary characters. For compatibility,
+/// a raw zero byte and a 4-byte UTF-8 sequence are accepted as well.
+///
+/// # Errors
+///
+/// This function returns an error if the bytes are ill-formed, e.g., contain
+/// an unpaired surrogate.
+pub fn decode(bytes: &[u8]) -> Result<Cow<'_, str>, Mutf8Error> {
+    match str::from_utf8(bytes) {
+        Ok(string) => Ok(Cow::Borrowed(string)),
+        Err(error) => {
+            let mut string = String::with_capacity(bytes.len());
+            decode_into(bytes, error.valid_up_to(), &mut string, false)?;
+            Ok(Cow::Owned(string))
+        }
+    }
+}
+
+/// Decodes MUTF-8 bytes into a string, replacing any ill-formed sequence with
+/// the [`REPLACEMENT_CHARACTER`] `U+FFFD`.
+///
+/// The bytes are borrowed if they are valid UTF-8, see [`decode`].
+#[must_use]
+pub fn decode_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    match str::from_utf8(bytes) {
+        Ok(string) => Cow::Borrowed(string),
+        Err(error) => {
+            let mut string = String::with_capacity(bytes.len());
+            // This never fails in the lossy mode.
+            let _ = decode_into(bytes, error.valid_up_to(), &mut string, true);
+            Cow::Owned(string)
+        }
+    }
+}
+
+/// Encodes a string into MUTF-8 bytes.
+///
+/// The string is borrowed if it contains neither null nor supplementary
+/// characters, in which case MUTF-8 is identical to UTF-8.
+#[must_use]
+pub fn encode(string: &str) -> Cow<'_, [u8]> {
+    let bytes = string.as_bytes();
+    // A supplementary character is the only one starting with `0xF0..=0xF4`.
+    match bytes.iter().position(|&byte| byte == 0 || byte >= 0xF0) {
+        None => Cow::Borrowed(bytes),
+        Some(valid_up_to) => {
+            let mut encoded = Vec::with_capacity(encoded_len(string));
+            encoded.extend_from_slice(&bytes[..valid_up_to]);
+            let mut buf = [0; 4];
+            for ch in string[valid_up_to..].chars() {
+                match ch {
+                    '\0' => encoded.extend_from_slice(&[0xC0, 0x80]),
+                    '\u{10000}'.. => {
+                        for unit in ch.encode_utf16(&mut [0; 2]) {
+                            encoded.extend_from_slice(&encode_unit(*unit));
+                        }
+                    }
+                    _ => encoded.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes()),
+                }
+            }
+            Cow::Owned(encoded)
+        }
+    }
+}
+
+/// Returns the length of the string encoded in MUTF-8, in bytes.
+#[must_use]
+pub fn encoded_len(string: &str) -> usize {
+    // A null character takes one more byte, and a supplementary character
+    // takes two more bytes.
+    string.len()
+        + string
+            .bytes()
+            .map(|byte| match byte {
+                0 => 1,
+                0xF0.. => 2,
+                _ => 0,
+            })
+            .sum::<usize>()
+}
+
+/// Encodes a code unit of the Basic Multilingual Plane into three bytes.
+fn encode_unit(unit: u16) -> [u8; 3] {
+    [
+        0xE0 | (unit >> 12) as u8,
+        0x80 | ((unit >> 6) & 0x3F) as u8,
+        0x80 | (unit & 0x3F) as u8,
+    ]
+}
+
+/// Decodes the bytes into the string, starting from the first byte that is
+/// not valid UTF-8.
+fn decode_into(
+    bytes: &[u8],
+    valid_up_to: usize,
+    string: &mut String,
+    lossy: bool,
+) -> Result<(), Mutf8Error> {
+    // SAFETY: The bytes before `valid_up_to` are checked by `str::from_utf8`.
+    string.push_str(unsafe { str::from_utf8_unchecked(&bytes[..valid_up_to]) });
+
+    let mut rest = &bytes[valid_up_to..];
+    while !rest.is_empty() {
+        let (ch, len) = match decode_char(rest) {
+            Ok(decoded) => decoded,
+            Err(len) if lossy => (REPLACEMENT_CHARACTER, len),
+            Err(_) => return Err(Mutf8Error(())),
+        };
+        string.push(ch);
+        rest = &rest[len..];
+    }
+    Ok(())
+}
+
+/// Decodes the first character of the non-empty bytes, returning it and its
+/// length in bytes, or the length of the ill-formed sequence to be skipped.
+fn decode_char(bytes: &[u8]) -> Result<(char, usize), usize> {
+    match *bytes {
+        [byte @ 0x00..=0x7F, ..] => Ok((char::from(byte), 1)),
+        // The two-byte null character, or an ordinary two-byte character.
+        [a @ 0xC0..=0xDF, b, ..] if is_cont(b) => {
+            let code = (u32::from(a & 0x1F) << 6) | u32::from(b & 0x3F);
+            match code {
+                0 | 0x80.. => Ok((char::from_u32(code).unwrap(), 2)),
+                _ => Err(2),
+            }
+        }
+        [a @ 0xE0..=0xEF, b, c, ..] if is_cont(b) && is_cont(c) => {
+            let unit = decode_unit(a, b, c);
+            match unit {
+                0..0x800 => Err(3),
+                // A high surrogate, which must be followed by a low surrogate.
+                0xD800..0xDC00 => match bytes[3..] {
+                    [d @ 0xED, e, f, ..] if is_cont(e) && is_cont(f) => {
+                        let low = decode_unit(d, e, f);
+                        if (0xDC00..0xE000).contains(&low) {
+                            let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                            Ok((char::from_u32(code).unwrap(), 6))
+                        } else {
+                            Err(3)
+                        }
+                    }
+                    _ => Err(3),
+                },
+                // An unpaired low surrogate.
+                0xDC00..0xE000 => Err(3),
+                _ => Ok((char::from_u32(unit).unwrap(), 3)),
+            }
+        }
+        // A four-byte UTF-8 sequence, which is accepted for compatibility.
+        [0xF0..=0xF4, ..] => match bytes.get(..4).map(str::from_utf8) {
+            Some(Ok(string)) => Ok((string.chars().next().unwrap(), 4)),
+            _ => Err(1),
+        },
+        _ => Err(1),
+    }
+}
+
+/// Returns `true` if the byte is a continuation byte, i.e., `0b10xxxxxx`.
+fn is_cont(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
+/// Decodes a three-byte code unit, which may be a surrogate.
+fn decode_unit(a: u8, b: u8, c: u8) -> u32 {
+    (u32::from(a & 0x0F) << 12) | (u32::from(b & 0x3F) << 6) | u32::from(c & 0x3F)
+}
+
+/// An error that is returned when cannot decode bytes as MUTF-8.
+///
+/// This would be returned by [`decode`].
+#[derive(Debug)]
+pub struct Mutf8Error(pub(crate) ());
+
+impl Display for Mutf8Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid modified UTF-8 sequence")
+    }
+}
+
+impl Error for Mutf8Error {}
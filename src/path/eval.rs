@@ -0,0 +1,400 @@
+//! The evaluation of the nodes of an [`NbtPath`](crate::path::NbtPath).
+
+use alloc::vec::Vec;
+
+use crate::kind::Kind;
+use crate::path::Node;
+use crate::value::{ByteArray, Compound, IntArray, List, LongArray, ThinSlice, Value, ValueRef};
+
+/// A mutable value selected by a node.
+///
+/// The elements of a typed list are not stored as [`Value`]s, so a target is
+/// either a container, whose children can be selected further, or the kind of
+/// any other value.
+pub(crate) enum Target<'a> {
+    Compound(&'a mut Compound),
+    List(&'a mut List),
+    ByteArray(&'a mut ByteArray),
+    IntArray(&'a mut IntArray),
+    LongArray(&'a mut LongArray),
+    /// A number or string.
+    Other(Kind),
+}
+
+impl<'a> Target<'a> {
+    fn new(value: &'a mut Value) -> Self {
+        match value {
+            Value::Compound(compound) => Target::Compound(compound),
+            Value::List(list) => Target::List(list),
+            Value::ByteArray(array) => Target::ByteArray(array),
+            Value::IntArray(array) => Target::IntArray(array),
+            Value::LongArray(array) => Target::LongArray(array),
+            value => Target::Other(value.kind()),
+        }
+    }
+
+    pub(super) fn kind(&self) -> Kind {
+        match self {
+            Target::Compound(_) => Kind::Compound,
+            Target::List(_) => Kind::List,
+            Target::ByteArray(_) => Kind::ByteArray,
+            Target::IntArray(_) => Kind::IntArray,
+            Target::LongArray(_) => Kind::LongArray,
+            Target::Other(kind) => *kind,
+        }
+    }
+
+    /// Returns the element kind and length of an array.
+    fn array(&self) -> Option<(Kind, usize)> {
+        match self {
+            Target::ByteArray(array) => Some((Kind::Byte, array.len())),
+            Target::IntArray(array) => Some((Kind::Int, array.len())),
+            Target::LongArray(array) => Some((Kind::Long, array.len())),
+            _ => None,
+        }
+    }
+}
+
+/// Selects the children of the value by the node.
+pub(super) fn select<'a>(node: &Node, value: ValueRef<'a>, output: &mut Vec<ValueRef<'a>>) {
+    match (node, value) {
+        (Node::MatchRoot(filter), ValueRef::Compound(compound))
+            if matches_compound(filter, compound) =>
+        {
+            output.push(value);
+        }
+        (Node::Key(key), ValueRef::Compound(compound)) => {
+            output.extend(compound.get(key).map(Value::as_borrowed));
+        }
+        (Node::MatchKey(key, filter), ValueRef::Compound(compound)) => output.extend(
+            compound
+                .get(key)
+                .map(Value::as_borrowed)
+                .filter(|&value| matches(filter, value)),
+        ),
+        (Node::Index(index), _) => {
+            let index = len(value).and_then(|len| resolve(len, *index));
+            output.extend(index.and_then(|index| element(value, index)));
+        }
+        (Node::All, _) => {
+            let len = len(value).unwrap_or(0);
+            output.extend((0..len).filter_map(|index| element(value, index)));
+        }
+        (Node::MatchElement(filter), ValueRef::List(list)) => {
+            output.extend(list.iter().filter(|&element| matches(filter, element)));
+        }
+        _ => {}
+    }
+}
+
+/// Selects the children of the target by the node, creating a missing child
+/// of the given kind like `getOrCreate` of vanilla.
+pub(crate) fn select_mut<'a>(
+    node: &Node,
+    target: Target<'a>,
+    create: Option<Kind>,
+    output: &mut Vec<Target<'a>>,
+) {
+    if let Some((kind, len)) = target.array() {
+        // The elements of an array have no children.
+        match node {
+            Node::Index(index) if resolve(len, *index).is_some() => {
+                output.push(Target::Other(kind))
+            }
+            Node::All => output.extend((0..len).map(|_| Target::Other(kind))),
+            _ => {}
+        }
+        return;
+    }
+    match (node, target) {
+        (Node::MatchRoot(filter), Target::Compound(compound))
+            if matches_compound(filter, compound) =>
+        {
+            output.push(Target::Compound(compound));
+        }
+        (Node::Key(key), Target::Compound(compound)) => {
+            match create {
+                Some(kind) if !compound.contains_key(key) => {
+                    compound.insert(key, Value::new(kind));
+                }
+                _ => {}
+            }
+            output.extend(compound.get_mut(key).map(Target::new));
+        }
+        (Node::MatchKey(key, filter), Target::Compound(compound)) => {
+            if create.is_some() && !compound.contains_key(key) {
+                compound.insert(key, filter.clone());
+            }
+            let value = compound.get_mut(key);
+            output.extend(
+                value
+                    .filter(|value| matches(filter, value.as_borrowed()))
+                    .map(Target::new),
+            );
+        }
+        (Node::Index(index), Target::List(list)) => {
+            if let Some(index) = resolve(list.len(), *index) {
+                output.extend(element_mut(list, index));
+            }
+        }
+        (Node::All, Target::List(list)) => {
+            if let (true, Some(kind)) = (list.is_empty(), create) {
+                // An empty list is always of any kind.
+                let _ = list.push(Value::new(kind));
+            }
+            output.extend(elements_mut(list));
+        }
+        (Node::MatchElement(filter), Target::List(list)) => {
+            if create.is_some() && !list.iter().any(|element| matches(filter, element)) {
+                // The filter is only added to a list of compounds.
+                let _ = list.push(filter.clone());
+            }
+            output.extend(
+                elements_mut(list)
+                    .into_iter()
+                    .filter(|element| matches!(element, Target::Compound(compound) if matches_compound(filter, compound))),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Sets the children of the target selected by the node, returning the
+/// number of changed values.
+pub(super) fn set(node: &Node, target: Target<'_>, value: &Value) -> usize {
+    match (node, target) {
+        (Node::Key(key), Target::Compound(compound)) => set_entry(compound, key, value),
+        (Node::MatchKey(key, filter), Target::Compound(compound)) => {
+            let matched = compound
+                .get(key)
+                .is_some_and(|value| matches(filter, value.as_borrowed()));
+            if matched {
+                set_entry(compound, key, value)
+            } else {
+                0
+            }
+        }
+        (Node::Index(index), Target::List(list)) => match resolve(list.len(), *index) {
+            Some(index) => set_element(list, index, value),
+            None => 0,
+        },
+        (Node::All, Target::List(list)) if list.is_empty() => {
+            usize::from(list.push(value.clone()).is_ok())
+        }
+        (Node::All, Target::List(list)) => (0..list.len())
+            .map(|index| set_element(list, index, value))
+            .sum(),
+        (Node::MatchElement(filter), Target::List(list)) => (0..list.len())
+            .filter(|&index| {
+                list.get(index)
+                    .is_some_and(|element| matches(filter, element))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|index| set_element(list, index, value))
+            .sum(),
+        (_, Target::ByteArray(array)) => set_array(node, array, value.as_byte()),
+        (_, Target::IntArray(array)) => set_array(node, array, value.as_int()),
+        (_, Target::LongArray(array)) => set_array(node, array, value.as_long()),
+        _ => 0,
+    }
+}
+
+fn set_entry(compound: &mut Compound, key: &str, value: &Value) -> usize {
+    if compound.get(key) == Some(value) {
+        return 0;
+    }
+    compound.insert(key, value.clone());
+    1
+}
+
+fn set_element(list: &mut List, index: usize, value: &Value) -> usize {
+    if list.get(index) == Some(value.as_borrowed()) {
+        return 0;
+    }
+    usize::from(list.set(index, value.clone()).is_ok())
+}
+
+/// Sets the elements of an array selected by `[index]` or `[]`, if the value
+/// is of the element kind.
+fn set_array<T: Copy + PartialEq>(
+    node: &Node,
+    array: &mut ThinSlice<T>,
+    value: Option<T>,
+) -> usize {
+    let Some(value) = value else {
+        return 0;
+    };
+    let range = match node {
+        Node::Index(index) => match resolve(array.len(), *index) {
+            Some(index) => index..index + 1,
+            None => return 0,
+        },
+        Node::All if array.is_empty() => {
+            *array = ThinSlice::from([value]);
+            return 1;
+        }
+        Node::All => 0..array.len(),
+        _ => return 0,
+    };
+    let mut count = 0;
+    for element in &mut array.as_mut_slice()[range] {
+        if *element != value {
+            *element = value;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Removes the children of the target selected by the node, returning the
+/// number of removed values.
+pub(super) fn remove(node: &Node, target: Target<'_>) -> usize {
+    match (node, target) {
+        (Node::Key(key), Target::Compound(compound)) => usize::from(compound.remove(key).is_some()),
+        (Node::MatchKey(key, filter), Target::Compound(compound)) => {
+            let matched = compound
+                .get(key)
+                .is_some_and(|value| matches(filter, value.as_borrowed()));
+            usize::from(matched && compound.remove(key).is_some())
+        }
+        (Node::Index(index), Target::List(list)) => {
+            let index = resolve(list.len(), *index);
+            usize::from(index.and_then(|index| list.remove(index)).is_some())
+        }
+        (Node::All, Target::List(list)) => {
+            let len = list.len();
+            list.clear();
+            len
+        }
+        (Node::MatchElement(filter), Target::List(list)) => {
+            let mut count = 0;
+            for index in (0..list.len()).rev() {
+                if list
+                    .get(index)
+                    .is_some_and(|element| matches(filter, element))
+                {
+                    list.remove(index);
+                    count += 1;
+                }
+            }
+            count
+        }
+        (_, Target::ByteArray(array)) => remove_array(node, array),
+        (_, Target::IntArray(array)) => remove_array(node, array),
+        (_, Target::LongArray(array)) => remove_array(node, array),
+        _ => 0,
+    }
+}
+
+/// Removes the elements of an array selected by `[index]` or `[]`.
+fn remove_array<T: Copy>(node: &Node, array: &mut ThinSlice<T>) -> usize {
+    match node {
+        Node::Index(index) => match resolve(array.len(), *index) {
+            Some(index) => {
+                let mut vec = array.to_vec();
+                vec.remove(index);
+                *array = ThinSlice::from(vec);
+                1
+            }
+            None => 0,
+        },
+        Node::All => {
+            let len = array.len();
+            *array = ThinSlice::new();
+            len
+        }
+        _ => 0,
+    }
+}
+
+/// Returns the index from the front, where a negative index counts from the
+/// back, or [`None`] if out of bounds.
+fn resolve(len: usize, index: i32) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(usize::try_from(index.unsigned_abs()).ok()?)?
+    } else {
+        usize::try_from(index).ok()?
+    };
+    (index < len).then_some(index)
+}
+
+/// Returns the number of elements of a list or array.
+fn len(value: ValueRef<'_>) -> Option<usize> {
+    match value {
+        ValueRef::List(list) => Some(list.len()),
+        ValueRef::ByteArray(array) => Some(array.len()),
+        ValueRef::IntArray(array) => Some(array.len()),
+        ValueRef::LongArray(array) => Some(array.len()),
+        _ => None,
+    }
+}
+
+/// Returns the element of a list or array.
+fn element(value: ValueRef<'_>, index: usize) -> Option<ValueRef<'_>> {
+    match value {
+        ValueRef::List(list) => list.get(index),
+        ValueRef::ByteArray(array) => array.get(index).copied().map(ValueRef::Byte),
+        ValueRef::IntArray(array) => array.get(index).copied().map(ValueRef::Int),
+        ValueRef::LongArray(array) => array.get(index).copied().map(ValueRef::Long),
+        _ => None,
+    }
+}
+
+fn element_mut(list: &mut List, index: usize) -> Option<Target<'_>> {
+    let kind = list.kind()?;
+    Some(match list {
+        List::ByteArray(vec) => Target::ByteArray(vec.get_mut(index)?),
+        List::List(vec) => Target::List(vec.get_mut(index)?),
+        List::Compound(vec) => Target::Compound(vec.get_mut(index)?),
+        List::IntArray(vec) => Target::IntArray(vec.get_mut(index)?),
+        List::LongArray(vec) => Target::LongArray(vec.get_mut(index)?),
+        List::Mixed(vec) => Target::new(vec.get_mut(index)?),
+        list if index < list.len() => Target::Other(kind),
+        _ => return None,
+    })
+}
+
+fn elements_mut(list: &mut List) -> Vec<Target<'_>> {
+    let Some(kind) = list.kind() else {
+        return Vec::new();
+    };
+    match list {
+        List::ByteArray(vec) => vec.iter_mut().map(Target::ByteArray).collect(),
+        List::List(vec) => vec.iter_mut().map(Target::List).collect(),
+        List::Compound(vec) => vec.iter_mut().map(Target::Compound).collect(),
+        List::IntArray(vec) => vec.iter_mut().map(Target::IntArray).collect(),
+        List::LongArray(vec) => vec.iter_mut().map(Target::LongArray).collect(),
+        List::Mixed(vec) => vec.iter_mut().map(Target::new).collect(),
+        list => (0..list.len()).map(|_| Target::Other(kind)).collect(),
+    }
+}
+
+/// Returns `true` if the value is a compound that matches the filter.
+fn matches(filter: &Compound, value: ValueRef<'_>) -> bool {
+    matches!(value, ValueRef::Compound(compound) if matches_compound(filter, compound))
+}
+
+/// Returns `true` if the compound contains a match of every entry of the
+/// filter, like `NbtUtils.compareNbt` of vanilla.
+fn matches_compound(filter: &Compound, compound: &Compound) -> bool {
+    filter.iter().all(|(key, pattern)| {
+        compound
+            .get(key)
+            .is_some_and(|value| compare(pattern.as_borrowed(), value.as_borrowed()))
+    })
+}
+
+/// Returns `true` if the value matches the pattern, where a non-empty list
+/// matches a list that contains a match of each of its elements.
+fn compare(pattern: ValueRef<'_>, value: ValueRef<'_>) -> bool {
+    match (pattern, value) {
+        (ValueRef::Compound(pattern), ValueRef::Compound(value)) => {
+            matches_compound(pattern, value)
+        }
+        (ValueRef::List(pattern), ValueRef::List(value)) if !pattern.is_empty() => pattern
+            .iter()
+            .all(|pattern| value.iter().any(|value| compare(pattern, value))),
+        _ => pattern == value,
+    }
+}
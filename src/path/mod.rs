@@ -0,0 +1,355 @@
+//! This module provides [`NbtPath`], the path language of the `/data` command
+//! and others, which selects values in a tree by keys, indices and filters.
+//!
+//! ```text
+//! Inventory[{Slot: 0b}].components."minecraft:custom_name"
+//! ```
+//!
+//! A path is a sequence of [`Node`]s. Each node selects values from the
+//! values selected by the previous node, starting from the root compound, so
+//! that a path may select any number of values:
+//!
+//! | Syntax          | Selects                                                  |
+//! |-----------------|----------------------------------------------------------|
+//! | `{Count: 1b}`   | the root, if it matches the filter (only as first node)  |
+//! | `key`, `"key"`  | the entry of the key of a compound                       |
+//! | `key{id: "a"}`  | the entry of the key, if it matches the filter           |
+//! | `[0]`, `[-1]`   | the element of a list or array, where `-1` is the last   |
+//! | `[]`            | all elements of a list or array                          |
+//! | `[{id: "a"}]`   | all compound elements of a list that match the filter    |
+//!
+//! The keys are separated by `.`, which is optional before `[` and `{`. An
+//! unquoted key may contain any character except spaces, quotes, `.`, `[`,
+//! `]`, `{` and `}`. A filter is an SNBT compound, which matches a compound
+//! that contains all of its entries, where a nested compound matches
+//! recursively, and a non-empty list matches a list that contains a match of
+//! each of its elements.
+//!
+//! ```
+//! use znbt::path::NbtPath;
+//! use znbt::value::{Compound, Value};
+//!
+//! let mut chest: Compound = r#"{Items: [{Slot: 0b, id: "minecraft:stone", count: 1}]}"#.parse()?;
+//! let path: NbtPath = r#"Items[{id: "minecraft:stone"}].count"#.parse()?;
+//! assert_eq!(path.set(&mut chest, &Value::Int(64)), 1);
+//! assert_eq!(path.get(&chest), [Value::Int(64).as_borrowed()]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+mod eval;
+
+pub(crate) use self::eval::{Target, select_mut};
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+use core::str::FromStr;
+
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::snbt::{Dialect, Parser, SnbtError};
+use crate::value::{Compound, MergeMode, Value, ValueRef};
+use crate::visit::write_key;
+
+/// A node of an [`NbtPath`], see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    /// `{...}`, which selects the root if it matches the filter. This is only
+    /// valid as the first node.
+    MatchRoot(Compound),
+    /// `key`, which selects the entry of the key of a compound.
+    Key(String),
+    /// `key{...}`, which selects the entry of the key of a compound if it
+    /// matches the filter.
+    MatchKey(String, Compound),
+    /// `[index]`, which selects the element of a list or array, where a
+    /// negative index counts from the back.
+    Index(i32),
+    /// `[]`, which selects all elements of a list or array.
+    All,
+    /// `[{...}]`, which selects all compound elements of a list that match
+    /// the filter.
+    MatchElement(Compound),
+}
+
+impl Node {
+    /// Returns the kind of a missing parent of the node to be created, i.e.,
+    /// a compound for a key and a list for an element.
+    fn parent_kind(&self) -> Kind {
+        match self {
+            Node::MatchRoot(_) | Node::Key(_) | Node::MatchKey(..) => Kind::Compound,
+            Node::Index(_) | Node::All | Node::MatchElement(_) => Kind::List,
+        }
+    }
+}
+
+/// A path of the `/data` command, see the [module documentation](self).
+///
+/// The [`Display`] of a path can be parsed back into the same path, where a
+/// key is quoted like [`visit::Path`](crate::visit::Path), and a filter is
+/// printed as compact SNBT.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NbtPath {
+    nodes: Vec<Node>,
+}
+
+impl NbtPath {
+    /// Parses a path, where the filters are SNBT of the
+    /// [legacy](Dialect::Legacy) dialect.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is not a well-formed path.
+    #[inline]
+    pub fn parse(input: &str) -> Result<Self, SnbtError> {
+        Self::parse_with(input, Dialect::Legacy)
+    }
+
+    /// Parses a path, where the filters are SNBT of the given dialect.
+    ///
+    /// The empty path is the root.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the input is not a well-formed path.
+    pub fn parse_with(input: &str, dialect: Dialect) -> Result<Self, SnbtError> {
+        let mut parser = Parser::new(input, dialect);
+        let mut nodes = Vec::new();
+        while parser.peek().is_some() {
+            nodes.push(parse_node(&mut parser, nodes.is_empty())?);
+            match parser.peek() {
+                None | Some(b'[' | b'{') => {}
+                Some(b'.') => parser.pos += 1,
+                Some(_) => return Err(parser.error("expected '.'")),
+            }
+        }
+        Ok(NbtPath { nodes })
+    }
+
+    /// Returns the nodes of the path.
+    #[inline]
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns the values selected by the path, in the order of the tree.
+    #[must_use]
+    pub fn get<'a>(&self, root: &'a Compound) -> Vec<ValueRef<'a>> {
+        let mut values = vec![ValueRef::Compound(root)];
+        for node in &self.nodes {
+            let mut selected = Vec::new();
+            for value in values {
+                eval::select(node, value, &mut selected);
+            }
+            values = selected;
+        }
+        values
+    }
+
+    /// Sets the values selected by the path to a clone of the value, returning
+    /// the number of values that changed.
+    ///
+    /// The missing parents are created like the `/data modify ... set`
+    /// command, i.e., a missing entry as a compound or list depending on the
+    /// next node, an element of an empty list for `[]`, and an element that
+    /// is a clone of the filter for `[{...}]` if no element matches. The
+    /// elements of a list are only set if the value is of the element kind.
+    pub fn set(&self, root: &mut Compound, value: &Value) -> usize {
+        match self.parents(root, true) {
+            (Some(last), parents) => parents
+                .into_iter()
+                .map(|parent| eval::set(last, parent, value))
+                .sum(),
+            (None, _) => 0,
+        }
+    }
+
+    /// Removes the values selected by the path, returning the number of
+    /// values removed.
+    pub fn remove(&self, root: &mut Compound) -> usize {
+        match self.parents(root, false) {
+            (Some(last), parents) => parents
+                .into_iter()
+                .map(|parent| eval::remove(last, parent))
+                .sum(),
+            (None, _) => 0,
+        }
+    }
+
+    /// Merges the compound into the compounds selected by the path, returning
+    /// the number of compounds that changed.
+    ///
+    /// The missing parents are created like [`NbtPath::set`], and a missing
+    /// value is created as an empty compound. Each selected compound is
+    /// merged like the `/data modify ... merge` command, see
+    /// [`Compound::merge`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a selected value is not a compound,
+    /// in which case nothing is merged.
+    #[inline]
+    pub fn merge(&self, root: &mut Compound, source: &Compound) -> Result<usize, NbtError> {
+        self.merge_with(root, source, MergeMode::Merge)
+    }
+
+    /// Merges the compound into the compounds selected by the path in the
+    /// given mode, returning the number of compounds that changed.
+    ///
+    /// This is like [`NbtPath::merge`], but each selected compound is merged
+    /// by [`Compound::merge_with`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a selected value is not a compound,
+    /// in which case nothing is merged.
+    pub fn merge_with(
+        &self,
+        root: &mut Compound,
+        source: &Compound,
+        mode: MergeMode,
+    ) -> Result<usize, NbtError> {
+        let targets = match self.parents(root, true) {
+            (Some(last), parents) => {
+                let mut targets = Vec::new();
+                for parent in parents {
+                    select_mut(last, parent, Some(Kind::Compound), &mut targets);
+                }
+                targets
+            }
+            (None, parents) => parents,
+        };
+        let mut compounds = Vec::with_capacity(targets.len());
+        for target in targets {
+            match target {
+                Target::Compound(compound) => compounds.push(compound),
+                target => {
+                    return Err(NbtError::UnexpectedKind {
+                        expected: Kind::Compound,
+                        found: Some(target.kind()),
+                    });
+                }
+            }
+        }
+        Ok(compounds
+            .into_iter()
+            .map(|compound| usize::from(compound.merge_with(source, mode)))
+            .sum())
+    }
+
+    /// Selects the parents of the values selected by the path, i.e., the
+    /// values selected by all nodes but the last, which is returned together.
+    fn parents<'a>(
+        &self,
+        root: &'a mut Compound,
+        create: bool,
+    ) -> (Option<&Node>, Vec<Target<'a>>) {
+        let mut targets = vec![Target::Compound(root)];
+        let Some((last, nodes)) = self.nodes.split_last() else {
+            return (None, targets);
+        };
+        for (i, node) in nodes.iter().enumerate() {
+            let kind = create.then(|| self.nodes[i + 1].parent_kind());
+            let mut selected = Vec::new();
+            for target in targets {
+                select_mut(node, target, kind, &mut selected);
+            }
+            targets = selected;
+        }
+        (Some(last), targets)
+    }
+}
+
+/// Parses a node, where a root filter is only allowed as the first node.
+fn parse_node(parser: &mut Parser<'_>, first: bool) -> Result<Node, SnbtError> {
+    match parser.peek() {
+        Some(b'{') if first => Ok(Node::MatchRoot(parser.parse_compound()?)),
+        Some(b'{') => Err(parser.error("unexpected filter")),
+        Some(b'[') => {
+            parser.pos += 1;
+            let node = match parser.peek() {
+                Some(b'{') => Node::MatchElement(parser.parse_compound()?),
+                Some(b']') => Node::All,
+                _ => {
+                    let start = parser.pos;
+                    let index = parser.take_while(|byte| byte == b'-' || byte.is_ascii_digit());
+                    Node::Index(index.parse().map_err(|_| {
+                        parser.pos = start;
+                        parser.error("invalid index")
+                    })?)
+                }
+            };
+            if parser.peek() != Some(b']') {
+                return Err(parser.error("expected ']'"));
+            }
+            parser.pos += 1;
+            Ok(node)
+        }
+        Some(b'"' | b'\'') => {
+            let key = parser.read_quoted()?;
+            parse_key_node(parser, key)
+        }
+        _ => match parser.take_while(is_unquoted_key) {
+            "" => Err(parser.error("expected key")),
+            key => parse_key_node(parser, String::from(key)),
+        },
+    }
+}
+
+/// Parses the filter after a key, if any.
+fn parse_key_node(parser: &mut Parser<'_>, key: String) -> Result<Node, SnbtError> {
+    if parser.peek() == Some(b'{') {
+        Ok(Node::MatchKey(key, parser.parse_compound()?))
+    } else {
+        Ok(Node::Key(key))
+    }
+}
+
+/// Returns `true` if the byte is allowed in an unquoted key.
+fn is_unquoted_key(byte: u8) -> bool {
+    !matches!(byte, b' ' | b'"' | b'\'' | b'.' | b'[' | b']' | b'{' | b'}')
+}
+
+impl From<Vec<Node>> for NbtPath {
+    /// Creates a path of the nodes, where [`Node::MatchRoot`] should only be
+    /// the first node.
+    #[inline]
+    fn from(nodes: Vec<Node>) -> Self {
+        NbtPath { nodes }
+    }
+}
+
+impl FromStr for NbtPath {
+    type Err = SnbtError;
+
+    /// Parses a path, see [`NbtPath::parse`].
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                Node::MatchRoot(filter) => write!(f, "{filter}")?,
+                Node::Key(key) | Node::MatchKey(key, _) => {
+                    if i > 0 {
+                        f.write_char('.')?;
+                    }
+                    write_key(f, key)?;
+                    if let Node::MatchKey(_, filter) = node {
+                        write!(f, "{filter}")?;
+                    }
+                }
+                Node::Index(index) => write!(f, "[{index}]")?,
+                Node::All => f.write_str("[]")?,
+                Node::MatchElement(filter) => write!(f, "[{filter}]")?,
+            }
+        }
+        Ok(())
+    }
+}
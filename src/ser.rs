@@ -0,0 +1,778 @@
+//! This module provides the serde [`Serializer`] that writes binary NBT
+//! directly, without building an intermediate value tree.
+//!
+//! The Rust types are mapped to the NBT kinds as follows:
+//!
+//! | Rust                                   | NBT                              |
+//! | -------------------------------------- | -------------------------------- |
+//! | `bool`                                 | [`Kind::Byte`], `0` or `1`       |
+//! | `i8`, `i16`, `i32`, `i64`              | `Byte`, `Short`, `Int`, `Long`   |
+//! | `u8`, `u16`, `u32`, `u64`              | the same, if in range            |
+//! | `f32`, `f64`                           | `Float`, `Double`                |
+//! | `char`, `str`                          | `String`                         |
+//! | bytes                                  | `ByteArray`                      |
+//! | the [`array`](mod@array) helpers       | the array kinds                  |
+//! | sequences and tuples                   | `List`                           |
+//! | structs and maps with string keys      | `Compound`                       |
+//! | `Option<T>`                            | `T`, or omitted in compounds     |
+//! | unit variants                          | `String` of the variant name     |
+//! | other enum variants                    | `Compound` of the variant name   |
+//!
+//! The unsigned integers are in range if not above the maximum of the signed
+//! integers of the same width, otherwise they are rejected rather than
+//! bit-cast, e.g., `200u8` is not written as `-56b`.
+//!
+//! The root tag must be a compound, unless the root is nameless, see
+//! [`Serializer::nameless`].
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::array;
+use crate::binary::{Flavor, Java, Write};
+use crate::error::NbtError;
+use crate::kind::Kind;
+
+/// Serializes the value as a binary NBT of minecraft Java Edition with an
+/// empty root name into the writer.
+///
+/// # Errors
+///
+/// This function returns an error if the writer fails, or the value cannot be
+/// represented in NBT, e.g., the root is not a struct or map, or an unsigned
+/// integer is above the maximum of the signed kind of the same width, e.g.,
+/// `200u8`, which is rejected rather than bit-cast to `-56b`.
+pub fn to_writer<W, T>(writer: &mut W, value: &T) -> Result<(), NbtError>
+where
+    W: Write + ?Sized,
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer::new(writer, Java))
+}
+
+/// Serializes the value as a binary NBT of minecraft Java Edition with an
+/// empty root name into a byte vector.
+///
+/// # Errors
+///
+/// This function returns an error if the value cannot be represented in NBT,
+/// e.g., the root is not a struct or map, or an unsigned integer is above the
+/// maximum of the signed kind of the same width, see [`to_writer`].
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, NbtError> {
+    let mut bytes = Vec::new();
+    to_writer(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+/// A serde serializer that writes binary NBT into a writer.
+///
+/// By default, the root tag is a compound named by an empty string.
+pub struct Serializer<'a, W: ?Sized, F> {
+    writer: &'a mut W,
+    flavor: F,
+    header: Header<'a>,
+    /// The array kind forced by the [`array`](mod@array) helpers.
+    array: Option<Kind>,
+}
+
+/// Tells what to write before the payload, which is deferred until the kind
+/// of the value is known.
+enum Header<'a> {
+    /// The root tag, which is a compound if named.
+    Root(Option<&'a str>),
+    /// An entry of a compound.
+    Field(&'a str),
+    /// An element of a list.
+    Element(&'a mut ListState),
+    /// Nothing, since the header is already written.
+    Written,
+}
+
+/// The state of a list being serialized, whose element kind is determined by
+/// the first element, or by the array kind.
+struct ListState {
+    kind: Option<Kind>,
+    len: usize,
+    count: usize,
+}
+
+impl<'a, W: Write + ?Sized, F: Flavor> Serializer<'a, W, F> {
+    /// Creates a new serializer with an empty root name.
+    #[inline]
+    pub fn new(writer: &'a mut W, flavor: F) -> Self {
+        Serializer {
+            writer,
+            flavor,
+            header: Header::Root(Some("")),
+            array: None,
+        }
+    }
+
+    /// Sets the name of the root compound.
+    #[inline]
+    #[must_use]
+    pub fn with_name(self, name: &'a str) -> Self {
+        Serializer {
+            header: Header::Root(Some(name)),
+            ..self
+        }
+    }
+
+    /// Makes the root tag nameless and of any kind, see
+    /// [`binary::read_nameless`](crate::binary::read_nameless).
+    #[inline]
+    #[must_use]
+    pub fn nameless(self) -> Self {
+        Serializer {
+            header: Header::Root(None),
+            ..self
+        }
+    }
+
+    /// Writes the header of a tag of the given kind.
+    fn begin(&mut self, kind: Kind) -> Result<(), NbtError> {
+        match &mut self.header {
+            Header::Root(Some(name)) => {
+                if kind != Kind::Compound {
+                    return Err(NbtError::UnexpectedKind {
+                        expected: Kind::Compound,
+                        found: Some(kind),
+                    });
+                }
+                self.writer.write_all(&[kind as u8])?;
+                write_str(self.writer, self.flavor, name)
+            }
+            Header::Root(None) => self.writer.write_all(&[kind as u8]),
+            Header::Field(name) => {
+                self.writer.write_all(&[kind as u8])?;
+                write_str(self.writer, self.flavor, name)
+            }
+            Header::Written => Ok(()),
+            Header::Element(state) => {
+                state.count += 1;
+                match state.kind {
+                    None => {
+                        state.kind = Some(kind);
+                        self.writer.write_all(&[kind as u8])?;
+                        self.flavor.write_len(self.writer, state.len)
+                    }
+                    Some(expected) if expected == kind => Ok(()),
+                    Some(expected) => Err(NbtError::UnexpectedKind {
+                        expected,
+                        found: Some(kind),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn serialize_compound(mut self) -> Result<SerializeCompound<'a, W, F>, NbtError> {
+        self.begin(Kind::Compound)?;
+        Ok(SerializeCompound {
+            writer: self.writer,
+            flavor: self.flavor,
+            key: String::new(),
+        })
+    }
+
+    fn serialize_list(mut self, len: Option<usize>) -> Result<SerializeList<'a, W, F>, NbtError> {
+        let len = len.ok_or(NbtError::Unsupported("sequence of unknown length"))?;
+        let kind = match self.array.take() {
+            // The elements of an array are written without headers.
+            Some(array) => {
+                self.begin(array)?;
+                self.flavor.write_len(self.writer, len)?;
+                Some(match array {
+                    Kind::ByteArray => Kind::Byte,
+                    Kind::IntArray => Kind::Int,
+                    _ => Kind::Long,
+                })
+            }
+            None => {
+                self.begin(Kind::List)?;
+                None
+            }
+        };
+        Ok(SerializeList {
+            writer: self.writer,
+            flavor: self.flavor,
+            state: ListState {
+                kind,
+                len,
+                count: 0,
+            },
+        })
+    }
+
+    /// Writes the header of a compound containing only the variant, and then
+    /// the header of the variant.
+    fn begin_variant(mut self, variant: &'static str, kind: Kind) -> Result<Self, NbtError> {
+        self.begin(Kind::Compound)?;
+        self.writer.write_all(&[kind as u8])?;
+        write_str(self.writer, self.flavor, variant)?;
+        Ok(Serializer {
+            header: Header::Written,
+            ..self
+        })
+    }
+}
+
+fn write_str<W: Write + ?Sized, F: Flavor>(
+    writer: &mut W,
+    flavor: F,
+    string: &str,
+) -> Result<(), NbtError> {
+    let bytes = flavor.encode_str(string);
+    flavor.write_str_len(writer, bytes.len())?;
+    writer.write_all(&bytes)
+}
+
+/// Writes *TAG_End*, which closes a compound.
+fn write_end<W: Write + ?Sized>(writer: &mut W) -> Result<(), NbtError> {
+    writer.write_all(&[0])
+}
+
+impl<'a, W: Write + ?Sized, F: Flavor> ser::Serializer for Serializer<'a, W, F> {
+    type Ok = ();
+    type Error = NbtError;
+    type SerializeSeq = SerializeList<'a, W, F>;
+    type SerializeTuple = SerializeList<'a, W, F>;
+    type SerializeTupleStruct = SerializeList<'a, W, F>;
+    type SerializeTupleVariant = SerializeVariant<SerializeList<'a, W, F>>;
+    type SerializeMap = SerializeCompound<'a, W, F>;
+    type SerializeStruct = SerializeCompound<'a, W, F>;
+    type SerializeStructVariant = SerializeVariant<SerializeCompound<'a, W, F>>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), NbtError> {
+        self.serialize_i8(i8::from(v))
+    }
+
+    fn serialize_i8(mut self, v: i8) -> Result<(), NbtError> {
+        self.begin(Kind::Byte)?;
+        self.writer.write_all(&v.to_be_bytes())
+    }
+
+    fn serialize_i16(mut self, v: i16) -> Result<(), NbtError> {
+        self.begin(Kind::Short)?;
+        self.flavor.write_i16(self.writer, v)
+    }
+
+    fn serialize_i32(mut self, v: i32) -> Result<(), NbtError> {
+        self.begin(Kind::Int)?;
+        self.flavor.write_i32(self.writer, v)
+    }
+
+    fn serialize_i64(mut self, v: i64) -> Result<(), NbtError> {
+        self.begin(Kind::Long)?;
+        self.flavor.write_i64(self.writer, v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), NbtError> {
+        let v = i8::try_from(v).map_err(|_| out_of_range(v, "i8"))?;
+        self.serialize_i8(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), NbtError> {
+        let v = i16::try_from(v).map_err(|_| out_of_range(v, "i16"))?;
+        self.serialize_i16(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), NbtError> {
+        let v = i32::try_from(v).map_err(|_| out_of_range(v, "i32"))?;
+        self.serialize_i32(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), NbtError> {
+        let v = i64::try_from(v).map_err(|_| out_of_range(v, "i64"))?;
+        self.serialize_i64(v)
+    }
+
+    fn serialize_f32(mut self, v: f32) -> Result<(), NbtError> {
+        self.begin(Kind::Float)?;
+        self.flavor.write_f32(self.writer, v)
+    }
+
+    fn serialize_f64(mut self, v: f64) -> Result<(), NbtError> {
+        self.begin(Kind::Double)?;
+        self.flavor.write_f64(self.writer, v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), NbtError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(mut self, v: &str) -> Result<(), NbtError> {
+        self.begin(Kind::String)?;
+        write_str(self.writer, self.flavor, v)
+    }
+
+    fn serialize_bytes(mut self, v: &[u8]) -> Result<(), NbtError> {
+        self.begin(Kind::ByteArray)?;
+        self.flavor.write_len(self.writer, v.len())?;
+        self.writer.write_all(v)
+    }
+
+    fn serialize_none(self) -> Result<(), NbtError> {
+        match self.header {
+            // A missing entry of a compound is simply omitted.
+            Header::Field(_) => Ok(()),
+            _ => Err(NbtError::Unsupported("`None` outside of compound")),
+        }
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), NbtError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), NbtError> {
+        Err(NbtError::Unsupported("`()`"))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), NbtError> {
+        let compound = self.serialize_compound()?;
+        write_end(compound.writer)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), NbtError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), NbtError> {
+        value.serialize(Serializer {
+            array: array::kind_of(name),
+            ..self
+        })
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), NbtError> {
+        let mut compound = self.serialize_compound()?;
+        ser::SerializeStruct::serialize_field(&mut compound, variant, value)?;
+        ser::SerializeStruct::end(compound)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, NbtError> {
+        self.serialize_list(len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, NbtError> {
+        self.serialize_list(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, NbtError> {
+        self.serialize_list(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, NbtError> {
+        let serializer = self.begin_variant(variant, Kind::List)?;
+        Ok(SerializeVariant(serializer.serialize_list(Some(len))?))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, NbtError> {
+        self.serialize_compound()
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, NbtError> {
+        self.serialize_compound()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, NbtError> {
+        let serializer = self.begin_variant(variant, Kind::Compound)?;
+        Ok(SerializeVariant(serializer.serialize_compound()?))
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), NbtError> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Serializes the entries of a compound.
+pub struct SerializeCompound<'a, W: ?Sized, F> {
+    writer: &'a mut W,
+    flavor: F,
+    key: String,
+}
+
+impl<'a, W: Write + ?Sized, F: Flavor> SerializeCompound<'a, W, F> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), NbtError> {
+        value.serialize(Serializer {
+            writer: &mut *self.writer,
+            flavor: self.flavor,
+            header: Header::Field(key),
+            array: None,
+        })
+    }
+
+    fn finish(self) -> Result<&'a mut W, NbtError> {
+        write_end(self.writer)?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write + ?Sized, F: Flavor> ser::SerializeMap for SerializeCompound<'_, W, F> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), NbtError> {
+        self.key.clear();
+        key.serialize(KeySerializer(&mut self.key))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        value.serialize(Serializer {
+            writer: &mut *self.writer,
+            flavor: self.flavor,
+            header: Header::Field(&self.key),
+            array: None,
+        })
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish().map(drop)
+    }
+}
+
+impl<W: Write + ?Sized, F: Flavor> ser::SerializeStruct for SerializeCompound<'_, W, F> {
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), NbtError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        self.finish().map(drop)
+    }
+}
+
+/// Serializes the elements of a list.
+pub struct SerializeList<'a, W: ?Sized, F> {
+    writer: &'a mut W,
+    flavor: F,
+    state: ListState,
+}
+
+impl<'a, W: Write + ?Sized, F: Flavor> SerializeList<'a, W, F> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        if self.state.count == self.state.len {
+            return Err(NbtError::Message(format!(
+                "expected {} elements, found more",
+                self.state.len
+            )));
+        }
+        value.serialize(Serializer {
+            writer: &mut *self.writer,
+            flavor: self.flavor,
+            header: Header::Element(&mut self.state),
+            array: None,
+        })
+    }
+
+    fn finish(self) -> Result<&'a mut W, NbtError> {
+        if self.state.count != self.state.len {
+            return Err(NbtError::Message(format!(
+                "expected {} elements, found {}",
+                self.state.len, self.state.count
+            )));
+        }
+        if self.state.kind.is_none() {
+            // An empty list is written as a list of *TAG_End*.
+            self.writer.write_all(&[0])?;
+            self.flavor.write_len(self.writer, 0)?;
+        }
+        Ok(self.writer)
+    }
+}
+
+macro_rules! impl_serialize_list {
+    ($($trait:ident::$method:ident),*) => {$(
+        impl<W: Write + ?Sized, F: Flavor> ser::$trait for SerializeList<'_, W, F> {
+            type Ok = ();
+            type Error = NbtError;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+                self.element(value)
+            }
+
+            fn end(self) -> Result<(), NbtError> {
+                self.finish().map(drop)
+            }
+        }
+    )*};
+}
+
+impl_serialize_list! {
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field
+}
+
+/// Serializes the payload of an enum variant, which is wrapped in a compound
+/// containing only the variant.
+pub struct SerializeVariant<S>(S);
+
+impl<W: Write + ?Sized, F: Flavor> ser::SerializeTupleVariant
+    for SerializeVariant<SerializeList<'_, W, F>>
+{
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NbtError> {
+        self.0.element(value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        write_end(self.0.finish()?)
+    }
+}
+
+impl<W: Write + ?Sized, F: Flavor> ser::SerializeStructVariant
+    for SerializeVariant<SerializeCompound<'_, W, F>>
+{
+    type Ok = ();
+    type Error = NbtError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), NbtError> {
+        self.0.field(key, value)
+    }
+
+    fn end(self) -> Result<(), NbtError> {
+        write_end(self.0.finish()?)
+    }
+}
+
+/// Serializes a map key into a string, since the keys of compounds are always
+/// strings.
+struct KeySerializer<'a>(&'a mut String);
+
+impl ser::Serializer for KeySerializer<'_> {
+    type Ok = ();
+    type Error = NbtError;
+    type SerializeSeq = Impossible<(), NbtError>;
+    type SerializeTuple = Impossible<(), NbtError>;
+    type SerializeTupleStruct = Impossible<(), NbtError>;
+    type SerializeTupleVariant = Impossible<(), NbtError>;
+    type SerializeMap = Impossible<(), NbtError>;
+    type SerializeStruct = Impossible<(), NbtError>;
+    type SerializeStructVariant = Impossible<(), NbtError>;
+
+    fn serialize_bool(self, _: bool) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), NbtError> {
+        self.0.push(v);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), NbtError> {
+        self.0.push_str(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), NbtError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), NbtError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, NbtError> {
+        Err(key_must_be_a_string())
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), NbtError> {
+        use core::fmt::Write as _;
+        write!(self.0, "{value}").map_err(ser::Error::custom)
+    }
+}
+
+/// Returns the error of an unsigned integer above the maximum of the signed
+/// integer of the same width.
+fn out_of_range(v: impl Display, kind: &str) -> NbtError {
+    NbtError::Message(format!("integer `{v}` out of range of `{kind}`"))
+}
+
+fn key_must_be_a_string() -> NbtError {
+    NbtError::Unsupported("map key other than string")
+}
+
+impl ser::Error for NbtError {
+    fn custom<T: Display>(message: T) -> Self {
+        NbtError::Message(message.to_string())
+    }
+}
@@ -0,0 +1,82 @@
+//! This module provides the parser and printer of *stringified NBT* (SNBT),
+//! which is the text format used by commands, e.g., `/data get`.
+//!
+//! ```text
+//! {name: "Steve", Health: 20.0f, Pos: [0.5d, 64.0d, -0.5d], UUID: [I; 1, 2, 3, 4]}
+//! ```
+//!
+//! A number is typed by its suffix, which is case-insensitive: `b` for
+//! [`Byte`](crate::kind::Kind::Byte), `s` for `Short`, no suffix for `Int`,
+//! `L` for `Long`, `f` for `Float`, and `d` or a decimal point without suffix
+//! for `Double`. `true` and `false` are bytes `1` and `0`. A token that is
+//! neither a number nor a boolean is an unquoted string.
+//!
+//! Java Edition 1.21.5 extends the grammar, which is selected by [`Dialect`].
+//!
+//! See [`parse`] and [`Printer`]. The value tree implements [`FromStr`] by
+//! [`parse`], and [`Display`] by [`Printer`].
+//!
+//! [`FromStr`]: core::str::FromStr
+
+mod parse;
+mod print;
+
+use core::error::Error;
+use core::fmt::{self, Display, Formatter};
+
+pub(crate) use self::parse::{Parser, is_unquoted};
+pub use self::parse::{parse, parse_with};
+pub use self::print::Printer;
+pub(crate) use self::print::quote;
+
+/// The dialect of SNBT, which changed in minecraft Java Edition 1.21.5.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// The grammar before Java Edition 1.21.5.
+    #[default]
+    Legacy,
+    /// The grammar since Java Edition 1.21.5, which is a superset of the
+    /// legacy one, except that:
+    ///
+    /// - An unquoted string cannot start with `0-9`, `+`, `-` or `.`, which is
+    ///   always a number, and an out of range number is an error.
+    /// - A list may contain elements of different kinds, which is a
+    ///   [`List::Mixed`](crate::value::List::Mixed).
+    /// - A quoted string may contain the escapes `\b`, `\s`, `\t`, `\n`,
+    ///   `\f`, `\r`, `\xHH`, `\uHHHH`, `\UHHHHHHHH` and `\N{name}`, where
+    ///   the last requires the `unicode-names` feature.
+    /// - An integer may be hexadecimal `0x1F` or binary `0b101`, with a
+    ///   signedness suffix `s` or `u` before the kind suffix, e.g., `255ub`,
+    ///   and `i` is the kind suffix of ints. The arrays accept integers
+    ///   without suffix, e.g., `[B; 1, 2]`.
+    /// - The digits of a number may be separated by underscores, e.g.,
+    ///   `1_000_000`.
+    /// - The operations `bool(number)` and `uuid(string)` are evaluated into a
+    ///   byte and an int array respectively.
+    V1_21_5,
+}
+
+/// An error that is returned when cannot parse SNBT.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnbtError {
+    position: usize,
+    message: &'static str,
+}
+
+impl SnbtError {
+    /// Returns the byte offset in the input where the error occurs.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for SnbtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for SnbtError {}
@@ -0,0 +1,644 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::snbt::{Dialect, SnbtError};
+use crate::value::{Compound, List, Value};
+
+/// Parses SNBT of the [legacy](Dialect::Legacy) dialect into a value.
+///
+/// Leading and trailing whitespaces are ignored.
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed SNBT,
+/// e.g., a list contains elements of different kinds.
+#[inline]
+pub fn parse(input: &str) -> Result<Value, SnbtError> {
+    parse_with(input, Dialect::Legacy)
+}
+
+/// Parses SNBT of the given dialect into a value.
+///
+/// Leading and trailing whitespaces are ignored.
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed SNBT of
+/// the dialect.
+pub fn parse_with(input: &str, dialect: Dialect) -> Result<Value, SnbtError> {
+    let mut parser = Parser::new(input, dialect);
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(value)
+}
+
+impl FromStr for Value {
+    type Err = SnbtError;
+
+    /// Parses SNBT into a value, see [`parse`].
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+impl FromStr for Compound {
+    type Err = SnbtError;
+
+    /// Parses SNBT into a compound, see [`parse`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse(s)? {
+            Value::Compound(compound) => Ok(*compound),
+            _ => Err(SnbtError {
+                position: 0,
+                message: "expected compound",
+            }),
+        }
+    }
+}
+
+/// Parses the values from a string.
+///
+/// This is also used to parse the compounds and quoted strings embedded in an
+/// [`NbtPath`](crate::path::NbtPath).
+pub(crate) struct Parser<'a> {
+    input: &'a str,
+    /// The byte offset of the next character.
+    pub(crate) pos: usize,
+    depth: usize,
+    dialect: Dialect,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a parser at the beginning of the input.
+    pub(crate) const fn new(input: &'a str, dialect: Dialect) -> Self {
+        Parser {
+            input,
+            pos: 0,
+            depth: 0,
+            dialect,
+        }
+    }
+
+    pub(crate) fn error(&self, message: &'static str) -> SnbtError {
+        SnbtError {
+            position: self.pos,
+            message,
+        }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), SnbtError> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(self.error(message));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Skips the separator between elements, returning `true` if present.
+    fn separator(&mut self) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+            self.skip_whitespace();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), SnbtError> {
+        self.depth += 1;
+        if self.depth > NbtError::MAX_DEPTH {
+            return Err(self.error("nested too deep"));
+        }
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Value, SnbtError> {
+        self.parse_value_or(Kind::Int)
+    }
+
+    /// Parses a value, where an integer without suffix is of the default kind
+    /// in the 1.21.5 dialect.
+    fn parse_value_or(&mut self, default: Kind) -> Result<Value, SnbtError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => Ok(Value::from(self.parse_compound()?)),
+            Some(b'[') => self.parse_list_or_array(),
+            Some(b'"' | b'\'') => Ok(Value::from(self.read_quoted()?)),
+            _ => match (self.read_unquoted(), self.dialect) {
+                ("", _) => Err(self.error("expected value")),
+                (token, Dialect::Legacy) => Ok(typed(token).unwrap_or_else(|| Value::from(token))),
+                (token, Dialect::V1_21_5) if self.peek() == Some(b'(') => {
+                    self.parse_operation(token, start)
+                }
+                ("true", Dialect::V1_21_5) => Ok(Value::Byte(1)),
+                ("false", Dialect::V1_21_5) => Ok(Value::Byte(0)),
+                (token, Dialect::V1_21_5) => match token.as_bytes()[0] {
+                    b'0'..=b'9' | b'+' | b'-' | b'.' => {
+                        number(token, default).map_err(|message| SnbtError {
+                            position: start,
+                            message,
+                        })
+                    }
+                    _ => Ok(Value::from(token)),
+                },
+            },
+        }
+    }
+
+    /// Parses an operation of the 1.21.5 dialect, e.g., `bool(1)`, after its
+    /// name.
+    fn parse_operation(&mut self, name: &str, start: usize) -> Result<Value, SnbtError> {
+        self.pos += 1;
+        self.skip_whitespace();
+        let mut args = Vec::new();
+        while self.peek() != Some(b')') {
+            args.push(self.parse_value()?);
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect(b')', "expected ')' or ','")?;
+        let error = |message| SnbtError {
+            position: start,
+            message,
+        };
+        match (name, &*args) {
+            ("bool", [value]) => match *value {
+                Value::Byte(value) => Ok(Value::from(value != 0)),
+                Value::Short(value) => Ok(Value::from(value != 0)),
+                Value::Int(value) => Ok(Value::from(value != 0)),
+                Value::Long(value) => Ok(Value::from(value != 0)),
+                Value::Float(value) => Ok(Value::from(value != 0.0)),
+                Value::Double(value) => Ok(Value::from(value != 0.0)),
+                _ => Err(error("expected number as argument of `bool`")),
+            },
+            ("uuid", [Value::String(uuid)]) => parse_uuid(uuid)
+                .map(|uuid| Value::from(&uuid[..]))
+                .ok_or_else(|| error("invalid UUID")),
+            ("uuid", _) => Err(error("expected string as argument of `uuid`")),
+            ("bool", _) => Err(error("expected one argument of `bool`")),
+            _ => Err(error("unknown operation")),
+        }
+    }
+
+    pub(crate) fn parse_compound(&mut self) -> Result<Compound, SnbtError> {
+        self.enter()?;
+        self.pos += 1;
+        self.skip_whitespace();
+        let mut compound = Compound::new();
+        while self.peek() != Some(b'}') {
+            let key = self.read_key()?;
+            self.expect(b':', "expected ':'")?;
+            let value = self.parse_value()?;
+            compound.insert(key, value);
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect(b'}', "expected '}' or ','")?;
+        self.depth -= 1;
+        Ok(compound)
+    }
+
+    fn parse_list_or_array(&mut self) -> Result<Value, SnbtError> {
+        self.enter()?;
+        self.pos += 1;
+        let bytes = self.input.as_bytes();
+        let value = match bytes.get(self.pos..self.pos + 2) {
+            Some(&[kind, b';']) if kind != b'"' && kind != b'\'' => {
+                let kind = match kind {
+                    b'B' => Kind::Byte,
+                    b'I' => Kind::Int,
+                    b'L' => Kind::Long,
+                    _ => return Err(self.error("invalid array type")),
+                };
+                self.pos += 2;
+                self.parse_array(kind)?
+            }
+            _ => Value::from(self.parse_list()?),
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn parse_list(&mut self) -> Result<List, SnbtError> {
+        self.skip_whitespace();
+        let mut values = Vec::new();
+        let mut mixed = false;
+        while self.peek() != Some(b']') {
+            let start = self.pos;
+            let value = self.parse_value()?;
+            if values
+                .first()
+                .is_some_and(|first: &Value| first.kind() != value.kind())
+            {
+                if self.dialect == Dialect::Legacy {
+                    self.pos = start;
+                    return Err(self.error("list contains elements of different kinds"));
+                }
+                mixed = true;
+            }
+            values.push(value);
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect(b']', "expected ']' or ','")?;
+        if mixed {
+            return Ok(List::Mixed(values));
+        }
+        // The elements are always of the same kind.
+        Ok(List::try_from(values).unwrap_or_default())
+    }
+
+    /// Parses the elements of an array after the prefix, e.g., `[I;`.
+    fn parse_array(&mut self, kind: Kind) -> Result<Value, SnbtError> {
+        self.skip_whitespace();
+        let mut list = List::with_kind(kind);
+        while self.peek() != Some(b']') {
+            let start = self.pos;
+            let value = self.parse_value_or(kind)?;
+            if list.push(value).is_err() {
+                self.pos = start;
+                return Err(self.error("array contains elements of different kinds"));
+            }
+            if !self.separator() {
+                break;
+            }
+        }
+        self.expect(b']', "expected ']' or ','")?;
+        // The list is always of the element kind of the array.
+        Ok(match list {
+            List::Byte(vec) => Value::from(vec),
+            List::Int(vec) => Value::from(vec),
+            List::Long(vec) => Value::from(vec),
+            _ => unreachable!(),
+        })
+    }
+
+    fn read_key(&mut self) -> Result<String, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'"' | b'\'') => self.read_quoted(),
+            _ => match self.read_unquoted() {
+                "" => Err(self.error("expected key")),
+                key => Ok(String::from(key)),
+            },
+        }
+    }
+
+    /// Reads an unquoted string, which may be empty.
+    fn read_unquoted(&mut self) -> &'a str {
+        self.take_while(is_unquoted)
+    }
+
+    /// Reads the longest string of the bytes that satisfy the predicate,
+    /// which must not split a character, i.e., either accepts all non-ASCII
+    /// bytes or none of them.
+    pub(crate) fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&predicate) {
+            self.pos += 1;
+        }
+        &self.input[start..self.pos]
+    }
+
+    /// Reads a string enclosed by the quote at the current position.
+    pub(crate) fn read_quoted(&mut self) -> Result<String, SnbtError> {
+        let quote = char::from(self.input.as_bytes()[self.pos]);
+        let mut string = String::new();
+        let mut rest = &self.input[self.pos + 1..];
+        loop {
+            let mut chars = rest.chars();
+            match chars.next() {
+                Some(ch) if ch == quote => break,
+                Some('\\') => {
+                    let escape = self.input.len() - rest.len();
+                    rest = chars.as_str();
+                    match unescape(&mut rest, quote, self.dialect) {
+                        Ok(ch) => string.push(ch),
+                        Err(message) => {
+                            self.pos = escape;
+                            return Err(self.error(message));
+                        }
+                    }
+                    continue;
+                }
+                Some(ch) => string.push(ch),
+                None => return Err(self.error("unclosed quoted string")),
+            }
+            rest = chars.as_str();
+        }
+        self.pos = self.input.len() - rest.len() + 1;
+        Ok(string)
+    }
+}
+
+/// Unescapes the sequence after a backslash, advancing the input past it.
+fn unescape(input: &mut &str, quote: char, dialect: Dialect) -> Result<char, &'static str> {
+    const INVALID: &str = "invalid escape sequence";
+
+    let mut chars = input.chars();
+    let ch = chars.next().ok_or(INVALID)?;
+    *input = chars.as_str();
+    match (ch, dialect) {
+        ('\\', _) => Ok('\\'),
+        (_, Dialect::Legacy) if ch == quote => Ok(ch),
+        (_, Dialect::Legacy) => Err(INVALID),
+        ('\'' | '"', _) => Ok(ch),
+        ('b', _) => Ok('\u{8}'),
+        ('s', _) => Ok(' '),
+        ('t', _) => Ok('\t'),
+        ('n', _) => Ok('\n'),
+        ('f', _) => Ok('\u{c}'),
+        ('r', _) => Ok('\r'),
+        ('x' | 'u' | 'U', _) => {
+            let len = match ch {
+                'x' => 2,
+                'u' => 4,
+                _ => 8,
+            };
+            let hex = input.get(..len).ok_or(INVALID)?;
+            if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(INVALID);
+            }
+            *input = &input[len..];
+            u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(INVALID)
+        }
+        ('N', _) => {
+            let (name, rest) = input
+                .strip_prefix('{')
+                .and_then(|input| input.split_once('}'))
+                .ok_or(INVALID)?;
+            *input = rest;
+            unicode_name(name)
+        }
+        _ => Err(INVALID),
+    }
+}
+
+#[cfg(feature = "unicode-names")]
+fn unicode_name(name: &str) -> Result<char, &'static str> {
+    unicode_names2::character(name).ok_or("unknown unicode character name")
+}
+
+#[cfg(not(feature = "unicode-names"))]
+fn unicode_name(_: &str) -> Result<char, &'static str> {
+    Err("unicode character names require the `unicode-names` feature")
+}
+
+/// Returns `true` if the byte is allowed in an unquoted string.
+pub(crate) fn is_unquoted(byte: u8) -> bool {
+    matches!(byte, b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z' | b'_' | b'-' | b'.' | b'+')
+}
+
+/// Types an unquoted token as a number or a boolean, returning [`None`] if it
+/// is a string, e.g., an out of range number.
+fn typed(token: &str) -> Option<Value> {
+    match token {
+        "true" => return Some(Value::Byte(1)),
+        "false" => return Some(Value::Byte(0)),
+        _ => {}
+    }
+    let (digits, suffix) = token.split_at(token.len() - 1);
+    match suffix {
+        "b" | "B" if is_integer(digits) => digits.parse::<i8>().ok().map(Value::Byte),
+        "s" | "S" if is_integer(digits) => digits.parse::<i16>().ok().map(Value::Short),
+        "l" | "L" if is_integer(digits) => digits.parse::<i64>().ok().map(Value::Long),
+        "f" | "F" if is_decimal(digits, false) => digits.parse::<f32>().ok().map(Value::Float),
+        "d" | "D" if is_decimal(digits, false) => digits.parse::<f64>().ok().map(Value::Double),
+        _ if is_integer(token) => token.parse::<i32>().ok().map(Value::Int),
+        _ if is_decimal(token, true) => token.parse::<f64>().ok().map(Value::Double),
+        _ => None,
+    }
+}
+
+/// Returns the rest after an optional sign and the leading digits, and the
+/// number of the digits.
+fn digits(token: &str) -> (&str, usize) {
+    let token = token.strip_prefix(['+', '-']).unwrap_or(token);
+    let len = token.bytes().take_while(u8::is_ascii_digit).count();
+    (&token[len..], len)
+}
+
+/// Returns `true` if the token is an integer without leading zeros, e.g.,
+/// `-12`.
+fn is_integer(token: &str) -> bool {
+    let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+    let (rest, len) = digits(token);
+    rest.is_empty() && len > 0 && (len == 1 || !unsigned.starts_with('0'))
+}
+
+/// Returns `true` if the token is a decimal number, e.g., `1.5e-3`, where the
+/// decimal point is required if `point` is `true`.
+fn is_decimal(token: &str, point: bool) -> bool {
+    let (rest, int_len) = digits(token);
+    let (rest, frac_len, has_point) = match rest.strip_prefix('.') {
+        Some(rest) => {
+            let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+            (&rest[len..], len, true)
+        }
+        None => (rest, 0, false),
+    };
+    if int_len + frac_len == 0 || (point && !has_point) {
+        return false;
+    }
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let (rest, len) = digits(exponent);
+            rest.is_empty() && len > 0
+        }
+        None => rest.is_empty(),
+    }
+}
+
+/// Parses a number of the 1.21.5 dialect, where an integer without suffix is
+/// of the default kind.
+///
+/// An integer may be hexadecimal `0x` or binary `0b`, and may have a suffix of
+/// signedness `s` or `u` before the kind suffix, e.g., `0xFFub`. The digits of
+/// any number may be separated by underscores, e.g., `1_000_000`.
+fn number(token: &str, default: Kind) -> Result<Value, &'static str> {
+    let (negative, body) = match token.as_bytes()[0] {
+        b'-' => (true, &token[1..]),
+        b'+' => (false, &token[1..]),
+        _ => (false, token),
+    };
+    let (radix, digits) = match body.get(..2) {
+        Some("0x" | "0X") => (16, &body[2..]),
+        Some("0b" | "0B") if body[2..].starts_with(['0', '1']) => (2, &body[2..]),
+        _ => (10, body),
+    };
+    match integer(negative, digits, radix, default) {
+        Err(_) if radix == 10 && is_float(body) => float(negative, body),
+        result => result,
+    }
+}
+
+fn integer(negative: bool, body: &str, radix: u32, default: Kind) -> Result<Value, &'static str> {
+    const INVALID: &str = "invalid number";
+    const OUT_OF_RANGE: &str = "number out of range";
+
+    let (digits, suffix) = split_digits(body, radix);
+    if !is_digits(digits) {
+        return Err(INVALID);
+    }
+    let kind = |byte: u8| match byte.to_ascii_lowercase() {
+        b'b' => Ok(Kind::Byte),
+        b's' => Ok(Kind::Short),
+        b'i' => Ok(Kind::Int),
+        b'l' => Ok(Kind::Long),
+        _ => Err(INVALID),
+    };
+    let (unsigned, kind) = match *suffix.as_bytes() {
+        [] => (false, default),
+        [b'u' | b'U'] => (true, default),
+        [byte] => (false, kind(byte)?),
+        [b'u' | b'U', byte] => (true, kind(byte)?),
+        [b's' | b'S', byte] => (false, kind(byte)?),
+        _ => return Err(INVALID),
+    };
+    let digits: String = digits.chars().filter(|&ch| ch != '_').collect();
+    let magnitude = u64::from_str_radix(&digits, radix).map_err(|_| OUT_OF_RANGE)?;
+    let bits = match kind {
+        Kind::Byte => 8,
+        Kind::Short => 16,
+        Kind::Int => 32,
+        _ => 64,
+    };
+    let value = if unsigned {
+        if negative && magnitude != 0 {
+            return Err("negative unsigned number");
+        }
+        if bits < 64 && magnitude >> bits != 0 {
+            return Err(OUT_OF_RANGE);
+        }
+        magnitude as i64
+    } else {
+        let limit = 1u64 << (bits - 1);
+        match negative {
+            true if magnitude <= limit => (magnitude as i64).wrapping_neg(),
+            false if magnitude < limit => magnitude as i64,
+            _ => return Err(OUT_OF_RANGE),
+        }
+    };
+    // The value is truncated to the bits of the kind, which reinterprets an
+    // unsigned value as signed.
+    Ok(match kind {
+        Kind::Byte => Value::Byte(value as i8),
+        Kind::Short => Value::Short(value as i16),
+        Kind::Int => Value::Int(value as i32),
+        _ => Value::Long(value),
+    })
+}
+
+/// Returns `true` if the body of a number is a decimal floating-point number,
+/// which has a decimal point, an exponent or a suffix `f` or `d`.
+fn is_float(body: &str) -> bool {
+    let mantissa = body.strip_suffix(['f', 'F', 'd', 'D']).unwrap_or(body);
+    let (int, rest) = split_digits(mantissa, 10);
+    let (frac, rest) = match rest.strip_prefix('.') {
+        Some(rest) => split_digits(rest, 10),
+        None if mantissa.len() == body.len() && !rest.starts_with(['e', 'E']) => return false,
+        None => ("", rest),
+    };
+    let exponent = match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let (digits, rest) =
+                split_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10);
+            rest.is_empty() && is_digits(digits)
+        }
+        None => rest.is_empty(),
+    };
+    exponent
+        && (int.is_empty() || is_digits(int))
+        && (frac.is_empty() || is_digits(frac))
+        && !(int.is_empty() && frac.is_empty())
+}
+
+fn float(negative: bool, body: &str) -> Result<Value, &'static str> {
+    let mut number = String::with_capacity(body.len() + 1);
+    if negative {
+        number.push('-');
+    }
+    number.extend(body.chars().filter(|&ch| ch != '_'));
+    let value = match number.strip_suffix(['f', 'F']) {
+        Some(mantissa) => mantissa
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .map(Value::Float),
+        None => {
+            let mantissa = number.strip_suffix(['d', 'D']).unwrap_or(&number);
+            mantissa
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(Value::Double)
+        }
+    };
+    value.ok_or("number out of range")
+}
+
+/// Splits the leading digits of the radix and underscores.
+fn split_digits(input: &str, radix: u32) -> (&str, &str) {
+    let len = input
+        .bytes()
+        .take_while(|&byte| byte == b'_' || char::from(byte).is_digit(radix))
+        .count();
+    input.split_at(len)
+}
+
+/// Returns `true` if the digits are not empty, and the underscores are only
+/// between the digits.
+fn is_digits(digits: &str) -> bool {
+    !digits.is_empty() && !digits.starts_with('_') && !digits.ends_with('_')
+}
+
+/// Parses a UUID in the hyphenated form, e.g.,
+/// `f81d4fae-7dec-11d0-a765-00a0c91e6bf6`, into four ints, most significant
+/// first.
+fn parse_uuid(uuid: &str) -> Option<[i32; 4]> {
+    let mut groups = uuid.split('-');
+    let mut value = 0u128;
+    for len in [8, 4, 4, 4, 12] {
+        let group = groups.next().filter(|group| group.len() == len)?;
+        if !group.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return None;
+        }
+        value = (value << (len * 4)) | u128::from_str_radix(group, 16).ok()?;
+    }
+    if groups.next().is_some() {
+        return None;
+    }
+    Some([
+        (value >> 96) as i32,
+        (value >> 64) as i32,
+        (value >> 32) as i32,
+        value as i32,
+    ])
+}
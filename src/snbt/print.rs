@@ -0,0 +1,311 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter, LowerExp, Write};
+
+use crate::snbt::parse::is_unquoted;
+use crate::value::{Compound, List, Value, ValueRef};
+
+/// The printer of SNBT, which is either compact or pretty.
+///
+/// The compact mode prints a value in a single line without spaces, which is
+/// the same as vanilla, e.g., `{id:"minecraft:stone",Count:1b}`. The pretty
+/// mode prints every entry of compounds and element of lists in its own line,
+/// with the given indentation.
+///
+/// In both modes, the keys are quoted only when needed, whereas the strings
+/// are always quoted by default like vanilla, so that the compact output is
+/// the same as vanilla. With [`Printer::with_unquoted_strings`], the strings
+/// are also quoted only when needed, i.e., when empty, containing characters
+/// other than `0-9`, `A-Z`, `a-z`, `_`, `-`, `.` and `+`, starting like a
+/// number, e.g. `1b`, or being `true` or `false`. The quote is `"`, unless
+/// the string contains `"` but no `'`.
+///
+/// The floats are printed like `Float.toString` and `Double.toString` of Java,
+/// i.e., with a decimal point, and in the scientific notation if less than
+/// `1e-3` or not less than `1e7` in magnitude, e.g., `0.5f` and `1.0E20d`.
+/// The non-finite floats cannot be printed, since SNBT has no syntax for
+/// them, so that [`Printer::print`] returns an error, and so does the
+/// [`Display`] of a value containing them, i.e., its `to_string` panics.
+///
+/// A [`List::Mixed`] is printed with its elements unwrapped, which can only be
+/// parsed by the 1.21.5 [`Dialect`](crate::snbt::Dialect).
+///
+/// The [`Display`] of the value tree prints in the compact mode, or in the
+/// pretty mode with four spaces if the alternate flag `{:#}` is given.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Printer<'a> {
+    indent: Option<&'a str>,
+    sort_keys: bool,
+    unquoted_strings: bool,
+}
+
+impl<'a> Printer<'a> {
+    /// Creates a printer of the compact mode.
+    #[inline]
+    #[must_use]
+    pub const fn compact() -> Self {
+        Printer {
+            indent: None,
+            sort_keys: false,
+            unquoted_strings: false,
+        }
+    }
+
+    /// Creates a printer of the pretty mode, indented by four spaces.
+    #[inline]
+    #[must_use]
+    pub const fn pretty() -> Self {
+        Printer {
+            indent: Some("    "),
+            sort_keys: false,
+            unquoted_strings: false,
+        }
+    }
+
+    /// Sets the indentation, which enables the pretty mode.
+    #[inline]
+    #[must_use]
+    pub const fn with_indent(self, indent: &'a str) -> Self {
+        Printer {
+            indent: Some(indent),
+            ..self
+        }
+    }
+
+    /// Sets whether to sort the entries of compounds by their keys, rather
+    /// than the insertion order.
+    #[inline]
+    #[must_use]
+    pub const fn with_sorted_keys(self, sort_keys: bool) -> Self {
+        Printer { sort_keys, ..self }
+    }
+
+    /// Sets whether to leave the strings unquoted when they are parsed back as
+    /// the same strings, rather than always quoting them like vanilla.
+    #[inline]
+    #[must_use]
+    pub const fn with_unquoted_strings(self, unquoted_strings: bool) -> Self {
+        Printer {
+            unquoted_strings,
+            ..self
+        }
+    }
+
+    /// Prints the value into the writer.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer fails, or the value
+    /// contains a non-finite float.
+    pub fn print<W: Write + ?Sized>(&self, writer: &mut W, value: ValueRef<'_>) -> fmt::Result {
+        Printing {
+            writer,
+            printer: self,
+            depth: 0,
+        }
+        .value(value)
+    }
+
+    /// Prints the value into a string.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the value contains a non-finite
+    /// float.
+    pub fn to_string(&self, value: ValueRef<'_>) -> Result<String, fmt::Error> {
+        let mut string = String::new();
+        self.print(&mut string, value)?;
+        Ok(string)
+    }
+}
+
+/// The state of printing a value.
+struct Printing<'a, W: ?Sized> {
+    writer: &'a mut W,
+    printer: &'a Printer<'a>,
+    depth: usize,
+}
+
+impl<W: Write + ?Sized> Printing<'_, W> {
+    fn value(&mut self, value: ValueRef<'_>) -> fmt::Result {
+        match value {
+            ValueRef::Byte(value) => write!(self.writer, "{value}b"),
+            ValueRef::Short(value) => write!(self.writer, "{value}s"),
+            ValueRef::Int(value) => write!(self.writer, "{value}"),
+            ValueRef::Long(value) => write!(self.writer, "{value}L"),
+            ValueRef::Float(value) => float(self.writer, value, 'f'),
+            ValueRef::Double(value) => float(self.writer, value, 'd'),
+            ValueRef::ByteArray(array) => self.array("B", array, "B"),
+            ValueRef::String(string) if self.printer.unquoted_strings && !needs_quotes(string) => {
+                self.writer.write_str(string)
+            }
+            ValueRef::String(string) => quote(self.writer, string),
+            ValueRef::List(list) => self.list(list),
+            ValueRef::Compound(compound) => self.compound(compound),
+            ValueRef::IntArray(array) => self.array("I", array, ""),
+            ValueRef::LongArray(array) => self.array("L", array, "L"),
+        }
+    }
+
+    fn array<T: Display>(&mut self, prefix: &str, array: &[T], suffix: &str) -> fmt::Result {
+        write!(self.writer, "[{prefix};")?;
+        for (i, element) in array.iter().enumerate() {
+            match (i, self.printer.indent) {
+                (0, None) => {}
+                (0, Some(_)) => self.writer.write_char(' ')?,
+                (_, None) => self.writer.write_char(',')?,
+                (_, Some(_)) => self.writer.write_str(", ")?,
+            }
+            write!(self.writer, "{element}{suffix}")?;
+        }
+        self.writer.write_char(']')
+    }
+
+    /// Writes a line break and the indentation in the pretty mode.
+    fn new_line(&mut self) -> fmt::Result {
+        if let Some(indent) = self.printer.indent {
+            self.writer.write_char('\n')?;
+            for _ in 0..self.depth {
+                self.writer.write_str(indent)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the elements between the brackets, each by the function.
+    fn elements<T>(
+        &mut self,
+        brackets: [char; 2],
+        elements: impl ExactSizeIterator<Item = T>,
+        mut write: impl FnMut(&mut Self, T) -> fmt::Result,
+    ) -> fmt::Result {
+        self.writer.write_char(brackets[0])?;
+        if elements.len() > 0 {
+            self.depth += 1;
+            for (i, element) in elements.enumerate() {
+                if i > 0 {
+                    self.writer.write_char(',')?;
+                }
+                self.new_line()?;
+                write(self, element)?;
+            }
+            self.depth -= 1;
+            self.new_line()?;
+        }
+        self.writer.write_char(brackets[1])
+    }
+
+    fn list(&mut self, list: &List) -> fmt::Result {
+        self.elements(['[', ']'], list.iter(), Self::value)
+    }
+
+    fn compound(&mut self, compound: &Compound) -> fmt::Result {
+        let mut entries: Vec<_> = compound.iter().collect();
+        if self.printer.sort_keys {
+            entries.sort_unstable_by_key(|&(key, _)| key);
+        }
+        self.elements(['{', '}'], entries.into_iter(), |this, (key, value)| {
+            if !key.is_empty() && key.bytes().all(is_unquoted) {
+                this.writer.write_str(key)?;
+            } else {
+                quote(this.writer, key)?;
+            }
+            this.writer.write_char(':')?;
+            if this.printer.indent.is_some() {
+                this.writer.write_char(' ')?;
+            }
+            this.value(value.as_borrowed())
+        })
+    }
+}
+
+/// Writes the float like Java, or returns an error if not finite.
+fn float<W, T>(writer: &mut W, value: T, suffix: char) -> fmt::Result
+where
+    W: Write + ?Sized,
+    T: Copy + Debug + LowerExp + Into<f64>,
+{
+    let magnitude = value.into().abs();
+    if !magnitude.is_finite() {
+        return Err(fmt::Error);
+    }
+    if magnitude == 0.0 || (1e-3..1e7).contains(&magnitude) {
+        // The debug format has a decimal point and no exponent in the range.
+        return write!(writer, "{value:?}{suffix}");
+    }
+    let scientific = format!("{value:e}");
+    let (mantissa, exponent) = scientific.split_once('e').ok_or(fmt::Error)?;
+    writer.write_str(mantissa)?;
+    if !mantissa.contains('.') {
+        writer.write_str(".0")?;
+    }
+    write!(writer, "E{exponent}{suffix}")
+}
+
+/// Returns `true` if the string is not parsed back as the same string when
+/// unquoted, e.g., `1b` or `true`.
+fn needs_quotes(string: &str) -> bool {
+    matches!(
+        string.as_bytes().first(),
+        None | Some(b'0'..=b'9' | b'+' | b'-' | b'.')
+    ) || !string.bytes().all(is_unquoted)
+        || string == "true"
+        || string == "false"
+}
+
+/// Writes the string enclosed by quotes, escaping the quotes and backslashes.
+pub(crate) fn quote<W: Write + ?Sized>(writer: &mut W, string: &str) -> fmt::Result {
+    let quote = if string.contains('"') && !string.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+    writer.write_char(quote)?;
+    for ch in string.chars() {
+        if ch == quote || ch == '\\' {
+            writer.write_char('\\')?;
+        }
+        writer.write_char(ch)?;
+    }
+    writer.write_char(quote)
+}
+
+/// Prints the value in the compact mode, or in the pretty mode if the
+/// alternate flag is given.
+fn display(value: ValueRef<'_>, f: &mut Formatter<'_>) -> fmt::Result {
+    let printer = if f.alternate() {
+        Printer::pretty()
+    } else {
+        Printer::compact()
+    };
+    printer.print(f, value)
+}
+
+impl Display for Value {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        display(self.as_borrowed(), f)
+    }
+}
+
+impl Display for ValueRef<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        display(*self, f)
+    }
+}
+
+impl Display for List {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        display(ValueRef::List(self), f)
+    }
+}
+
+impl Display for Compound {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        display(ValueRef::Compound(self), f)
+    }
+}
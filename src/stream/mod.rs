@@ -0,0 +1,30 @@
+//! This module provides the streaming reader and writer of binary NBT, which
+//! process the tags in bounded memory rather than building a value tree.
+//!
+//! The [`Writer`] is a push encoder, which writes the tags one by one into a
+//! byte sink, checking that the kinds and lengths of lists agree. The
+//! [`Reader`] is a pull parser, which yields the tags as a sequence of
+//! [`Event`]s from a buffered stream, e.g., a file of concatenated root tags:
+//!
+//! ```
+//! use znbt::stream::{Event, Reader};
+//! use znbt::value::Compound;
+//!
+//! let mut bytes = Vec::new();
+//! let compound = Compound::from_iter([("name", "Bananrama")]);
+//! znbt::binary::write(&mut bytes, "hello world", &compound, znbt::binary::Java)?;
+//!
+//! let mut reader = Reader::new(&bytes[..], znbt::binary::Java);
+//! while let Some(event) = reader.next_event()? {
+//!     if let Event::Key(key) = event {
+//!         println!("{key}");
+//!     }
+//! }
+//! # Ok::<(), znbt::error::NbtError>(())
+//! ```
+
+mod read;
+mod write;
+
+pub use self::read::{Event, Primitive, Reader};
+pub use self::write::Writer;
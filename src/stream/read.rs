@@ -0,0 +1,347 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::io::{BufRead, ErrorKind, Read};
+
+use crate::binary::{Flavor, take_array};
+use crate::error::NbtError;
+use crate::kind::Kind;
+
+/// An event of the [`Reader`].
+///
+/// A tag is either a single [`Event::Value`], or a sequence of events between
+/// a begin event and the matching end event. A named tag, i.e., an entry of a
+/// compound or a named root tag, is preceded by an [`Event::Key`].
+///
+/// For example, `{a: [1, 2], b: "x"}` yields `BeginCompound`, `Key("a")`,
+/// `BeginList(Some(Int), 2)`, `Value(Int, Int(1))`, `Value(Int, Int(2))`,
+/// `EndList`, `Key("b")`, `Value(String, String("x"))` and `EndCompound`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event<'a> {
+    /// The name of the next tag.
+    Key(&'a str),
+    /// A number or string, together with its kind.
+    Value(Kind, Primitive<'a>),
+    /// The beginning of a compound.
+    BeginCompound,
+    /// The end of a compound.
+    EndCompound,
+    /// The beginning of a list of the element kind and length, where [`None`]
+    /// means *TAG_End*, followed by the elements.
+    BeginList(Option<Kind>, usize),
+    /// The end of a list.
+    EndList,
+    /// The beginning of an array of the kind and length, followed by the
+    /// elements as [`Event::Value`]s of bytes, ints or longs respectively.
+    BeginArray(Kind, usize),
+    /// The end of an array.
+    EndArray,
+}
+
+/// A number or string of an [`Event::Value`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Primitive<'a> {
+    /// See [`Kind::Byte`].
+    Byte(i8),
+    /// See [`Kind::Short`].
+    Short(i16),
+    /// See [`Kind::Int`].
+    Int(i32),
+    /// See [`Kind::Long`].
+    Long(i64),
+    /// See [`Kind::Float`].
+    Float(f32),
+    /// See [`Kind::Double`].
+    Double(f64),
+    /// See [`Kind::String`].
+    String(&'a str),
+}
+
+impl Primitive<'_> {
+    /// Returns the kind of the value.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            Primitive::Byte(_) => Kind::Byte,
+            Primitive::Short(_) => Kind::Short,
+            Primitive::Int(_) => Kind::Int,
+            Primitive::Long(_) => Kind::Long,
+            Primitive::Float(_) => Kind::Float,
+            Primitive::Double(_) => Kind::Double,
+            Primitive::String(_) => Kind::String,
+        }
+    }
+}
+
+/// A container that the reader is inside.
+#[derive(Clone, Copy, Debug)]
+enum Frame {
+    Compound,
+    List(Option<Kind>, usize),
+    Array(Kind, usize),
+}
+
+/// A pull parser of binary NBT, which reads [`Event`]s from a buffered stream.
+///
+/// The memory is bounded by the nesting depth, which is limited by
+/// [`NbtError::MAX_DEPTH`], and the longest string. In particular, the arrays
+/// are yielded element by element.
+///
+/// The reader reads root tags one after another until the end of the stream,
+/// so that concatenated tags can be processed in a single loop. The root tags
+/// are named compounds by default, like [`read`](crate::binary::read), or
+/// nameless tags of any kind, see [`Reader::nameless`].
+#[derive(Debug)]
+pub struct Reader<R, F> {
+    reader: R,
+    flavor: F,
+    named: bool,
+    stack: Vec<Frame>,
+    /// The kind of the tag after [`Event::Key`].
+    pending: Option<Kind>,
+    bytes: Vec<u8>,
+    string: String,
+}
+
+impl<R: BufRead, F: Flavor> Reader<R, F> {
+    /// Creates a reader of named root tags from the buffered stream.
+    #[inline]
+    #[must_use]
+    pub const fn new(reader: R, flavor: F) -> Self {
+        Reader {
+            reader,
+            flavor,
+            named: true,
+            stack: Vec::new(),
+            pending: None,
+            bytes: Vec::new(),
+            string: String::new(),
+        }
+    }
+
+    /// Reads nameless root tags instead, see
+    /// [`read_nameless`](crate::binary::read_nameless).
+    #[inline]
+    #[must_use]
+    pub fn nameless(self) -> Self {
+        Reader {
+            named: false,
+            ..self
+        }
+    }
+
+    /// Returns the number of compounds, lists and arrays that the reader is
+    /// currently inside.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Unwraps the reader, returning the underlying stream.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads the next event, or returns [`None`] if the stream ends between
+    /// two root tags.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the stream fails, or the input is not
+    /// a well-formed binary NBT. The reader should not be used after an error.
+    pub fn next_event(&mut self) -> Result<Option<Event<'_>>, NbtError> {
+        if let Some(kind) = self.pending.take() {
+            return self.begin(kind).map(Some);
+        }
+        let event = match self.stack.last().copied() {
+            None => {
+                if self.reader.fill_buf()?.is_empty() {
+                    return Ok(None);
+                }
+                let kind = match self.read_kind()? {
+                    // A named root tag is always a compound.
+                    Some(kind) if !self.named || kind == Kind::Compound => kind,
+                    found => {
+                        return Err(NbtError::UnexpectedKind {
+                            expected: Kind::Compound,
+                            found,
+                        });
+                    }
+                };
+                if !self.named {
+                    return self.begin(kind).map(Some);
+                }
+                self.pending = Some(kind);
+                Event::Key(self.read_str()?)
+            }
+            Some(Frame::Compound) => match self.read_kind()? {
+                None => {
+                    self.stack.pop();
+                    Event::EndCompound
+                }
+                Some(kind) => {
+                    self.pending = Some(kind);
+                    Event::Key(self.read_str()?)
+                }
+            },
+            Some(Frame::List(_, 0)) => {
+                self.stack.pop();
+                Event::EndList
+            }
+            Some(Frame::Array(_, 0)) => {
+                self.stack.pop();
+                Event::EndArray
+            }
+            Some(Frame::List(kind, _)) => {
+                self.count_down();
+                // A non-empty list always has an element kind.
+                let kind = kind.ok_or(NbtError::UnexpectedKind {
+                    expected: Kind::List,
+                    found: None,
+                })?;
+                self.begin(kind)?
+            }
+            Some(Frame::Array(kind, _)) => {
+                self.count_down();
+                let element = match kind {
+                    Kind::ByteArray => Kind::Byte,
+                    Kind::IntArray => Kind::Int,
+                    _ => Kind::Long,
+                };
+                self.begin(element)?
+            }
+        };
+        Ok(Some(event))
+    }
+
+    /// Reads the payload of the kind, or its beginning for a container.
+    fn begin(&mut self, kind: Kind) -> Result<Event<'_>, NbtError> {
+        let primitive = match kind {
+            Kind::Byte => Primitive::Byte(self.read_with(read_i8)?),
+            Kind::Short => Primitive::Short(self.read_with(F::read_i16)?),
+            Kind::Int => Primitive::Int(self.read_with(F::read_i32)?),
+            Kind::Long => Primitive::Long(self.read_with(F::read_i64)?),
+            Kind::Float => Primitive::Float(self.read_with(F::read_f32)?),
+            Kind::Double => Primitive::Double(self.read_with(F::read_f64)?),
+            Kind::String => Primitive::String(self.read_str()?),
+            Kind::List => {
+                self.enter()?;
+                let element = self.read_kind()?;
+                let len = self.read_with(F::read_len)?;
+                if element.is_none() && len > 0 {
+                    return Err(NbtError::UnexpectedKind {
+                        expected: Kind::List,
+                        found: None,
+                    });
+                }
+                self.stack.push(Frame::List(element, len));
+                return Ok(Event::BeginList(element, len));
+            }
+            Kind::Compound => {
+                self.enter()?;
+                self.stack.push(Frame::Compound);
+                return Ok(Event::BeginCompound);
+            }
+            Kind::ByteArray | Kind::IntArray | Kind::LongArray => {
+                self.enter()?;
+                let len = self.read_with(F::read_len)?;
+                self.stack.push(Frame::Array(kind, len));
+                return Ok(Event::BeginArray(kind, len));
+            }
+        };
+        Ok(Event::Value(kind, primitive))
+    }
+
+    /// Counts down the remaining elements of the innermost list or array.
+    fn count_down(&mut self) {
+        if let Some(Frame::List(_, remaining) | Frame::Array(_, remaining)) = self.stack.last_mut()
+        {
+            *remaining -= 1;
+        }
+    }
+
+    fn enter(&self) -> Result<(), NbtError> {
+        if self.stack.len() >= NbtError::MAX_DEPTH {
+            return Err(NbtError::DepthLimit);
+        }
+        Ok(())
+    }
+
+    /// Reads a tag ID, where [`None`] means *TAG_End*.
+    fn read_kind(&mut self) -> Result<Option<Kind>, NbtError> {
+        let mut id = [0];
+        read_exact(&mut self.reader, &mut id)?;
+        match id {
+            [0] => Ok(None),
+            [id] => Ok(Some(Kind::new(id)?)),
+        }
+    }
+
+    fn read_str(&mut self) -> Result<&str, NbtError> {
+        let len = self.read_with(F::read_str_len)?;
+        // The bytes are read incrementally rather than allocated by the length
+        // up front, which may be up to 4 GiB for a malformed VarInt.
+        self.bytes.clear();
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.bytes)?;
+        if self.bytes.len() < len {
+            return Err(NbtError::UnexpectedEof);
+        }
+        let string = self.flavor.decode_str(&self.bytes)?;
+        self.string.clear();
+        self.string.push_str(&string);
+        Ok(&self.string)
+    }
+
+    /// Reads a number by the function from the buffer of the stream.
+    ///
+    /// A number that spans the end of the buffer is read byte by byte, which
+    /// supports the variable-length numbers of any flavor.
+    fn read_with<T>(
+        &mut self,
+        read: fn(F, &mut &[u8]) -> Result<T, NbtError>,
+    ) -> Result<T, NbtError> {
+        let buf = self.reader.fill_buf()?;
+        let mut input = buf;
+        match read(self.flavor, &mut input) {
+            Ok(value) => {
+                let len = buf.len() - input.len();
+                self.reader.consume(len);
+                return Ok(value);
+            }
+            Err(NbtError::UnexpectedEof) if buf.len() < MAX_NUMBER_LEN => {}
+            Err(error) => return Err(error),
+        }
+        let mut number = [0; MAX_NUMBER_LEN];
+        let mut len = buf.len();
+        number[..len].copy_from_slice(buf);
+        self.reader.consume(len);
+        loop {
+            read_exact(&mut self.reader, &mut number[len..=len])?;
+            len += 1;
+            match read(self.flavor, &mut &number[..len]) {
+                Err(NbtError::UnexpectedEof) if len < MAX_NUMBER_LEN => {}
+                result => return result,
+            }
+        }
+    }
+}
+
+/// The maximum length of an encoded number, i.e., a VarInt of 64 bits.
+const MAX_NUMBER_LEN: usize = 10;
+
+fn read_i8<F>(_: F, input: &mut &[u8]) -> Result<i8, NbtError> {
+    Ok(i8::from_be_bytes(take_array(input)?))
+}
+
+/// Reads exactly enough bytes to fill the buffer, where an early end of the
+/// stream is [`NbtError::UnexpectedEof`].
+fn read_exact<R: BufRead>(reader: &mut R, buf: &mut [u8]) -> Result<(), NbtError> {
+    reader.read_exact(buf).map_err(|error| match error.kind() {
+        ErrorKind::UnexpectedEof => NbtError::UnexpectedEof,
+        _ => NbtError::Io(error),
+    })
+}
@@ -0,0 +1,420 @@
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::binary::{Flavor, Write, write_payload};
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::Value;
+
+/// A container that the writer is inside.
+#[derive(Clone, Copy, Debug)]
+enum Frame {
+    Compound,
+    List {
+        kind: Option<Kind>,
+        len: usize,
+        count: usize,
+    },
+}
+
+/// A push encoder of binary NBT, which writes the tags directly into a byte
+/// sink without building a value tree.
+///
+/// The entries of a compound, and a named root tag, are written by the
+/// methods taking a name, e.g., [`Writer::field_int`], whereas the elements
+/// of a list, and a nameless root tag, are written by the `element_*` methods,
+/// e.g., [`Writer::element_int`]. The writer checks that:
+///
+/// - The entries are written inside compounds, and the elements inside lists.
+/// - The elements are of the element kind of the list.
+/// - The number of elements is the declared length of the list.
+/// - The compounds and lists are ended in order.
+///
+/// ```
+/// use znbt::kind::Kind;
+/// use znbt::stream::Writer;
+///
+/// let mut writer = Writer::new(Vec::new(), znbt::binary::Java);
+/// writer.begin_compound("")?;
+/// writer.field_string("id", "minecraft:pig")?;
+/// writer.begin_list("Pos", Some(Kind::Double), 3)?;
+/// for x in [0.5, 64.0, -0.5] {
+///     writer.element_double(x)?;
+/// }
+/// writer.end_list()?;
+/// writer.end_compound()?;
+/// let bytes = writer.finish()?;
+///
+/// let (_, compound) = znbt::binary::read(&mut &bytes[..], znbt::binary::Java)?;
+/// assert_eq!(compound.get("id").and_then(|v| v.as_str()), Some("minecraft:pig"));
+/// # Ok::<(), znbt::error::NbtError>(())
+/// ```
+///
+/// After a root tag is ended, another root tag can be written, like the
+/// concatenated tags read by [`Reader`](crate::stream::Reader).
+#[derive(Debug)]
+pub struct Writer<W, F> {
+    writer: W,
+    flavor: F,
+    named: bool,
+    stack: Vec<Frame>,
+}
+
+macro_rules! impl_numbers {
+    ($($field:ident, $element:ident, $kind:ident, $ty:ty, $write:expr;)*) => {$(
+        #[doc = concat!("Writes an entry of [`Kind::", stringify!($kind), "`].")]
+        ///
+        /// # Errors
+        ///
+        /// This function returns an error if the writer is not inside a
+        /// compound, or the output fails.
+        pub fn $field(&mut self, name: &str, value: $ty) -> Result<(), NbtError> {
+            self.field(name, Kind::$kind)?;
+            $write(self.flavor, &mut self.writer, value)
+        }
+
+        #[doc = concat!("Writes an element of [`Kind::", stringify!($kind), "`].")]
+        ///
+        /// # Errors
+        ///
+        /// This function returns an error if the writer is not inside a list
+        /// of the kind, the list is already full, or the output fails.
+        pub fn $element(&mut self, value: $ty) -> Result<(), NbtError> {
+            self.element(Kind::$kind)?;
+            $write(self.flavor, &mut self.writer, value)
+        }
+    )*};
+}
+
+macro_rules! impl_arrays {
+    ($($field:ident, $element:ident, $kind:ident, $ty:ty, $write:expr;)*) => {$(
+        #[doc = concat!("Writes an entry of [`Kind::", stringify!($kind), "`].")]
+        ///
+        /// # Errors
+        ///
+        /// This function returns an error if the writer is not inside a
+        /// compound, the array is too long, or the output fails.
+        pub fn $field(&mut self, name: &str, array: &[$ty]) -> Result<(), NbtError> {
+            self.field(name, Kind::$kind)?;
+            self.write_array(array, $write)
+        }
+
+        #[doc = concat!("Writes an element of [`Kind::", stringify!($kind), "`].")]
+        ///
+        /// # Errors
+        ///
+        /// This function returns an error if the writer is not inside a list
+        /// of the kind, the list is already full, the array is too long, or
+        /// the output fails.
+        pub fn $element(&mut self, array: &[$ty]) -> Result<(), NbtError> {
+            self.element(Kind::$kind)?;
+            self.write_array(array, $write)
+        }
+    )*};
+}
+
+impl<W: Write, F: Flavor> Writer<W, F> {
+    /// Creates a writer of named root tags into the byte sink.
+    #[inline]
+    #[must_use]
+    pub const fn new(writer: W, flavor: F) -> Self {
+        Writer {
+            writer,
+            flavor,
+            named: true,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Writes nameless root tags instead, see
+    /// [`write_nameless`](crate::binary::write_nameless).
+    #[inline]
+    #[must_use]
+    pub fn nameless(self) -> Self {
+        Writer {
+            named: false,
+            ..self
+        }
+    }
+
+    /// Returns the number of compounds and lists that the writer is currently
+    /// inside.
+    #[inline]
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Finishes writing, returning the underlying byte sink.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a compound or list is not ended.
+    pub fn finish(self) -> Result<W, NbtError> {
+        if !self.stack.is_empty() {
+            return Err(NbtError::InvalidState("unended compound or list"));
+        }
+        Ok(self.writer)
+    }
+
+    impl_numbers! {
+        field_byte, element_byte, Byte, i8, write_i8;
+        field_short, element_short, Short, i16, F::write_i16;
+        field_int, element_int, Int, i32, F::write_i32;
+        field_long, element_long, Long, i64, F::write_i64;
+        field_float, element_float, Float, f32, F::write_f32;
+        field_double, element_double, Double, f64, F::write_f64;
+    }
+
+    impl_arrays! {
+        field_byte_array, element_byte_array, ByteArray, i8, write_i8;
+        field_int_array, element_int_array, IntArray, i32, F::write_i32;
+        field_long_array, element_long_array, LongArray, i64, F::write_i64;
+    }
+
+    /// Writes an entry of [`Kind::String`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a compound,
+    /// the string is too long, or the output fails.
+    pub fn field_string(&mut self, name: &str, value: &str) -> Result<(), NbtError> {
+        self.field(name, Kind::String)?;
+        self.write_str(value)
+    }
+
+    /// Writes an element of [`Kind::String`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a list of
+    /// strings, the list is already full, the string is too long, or the
+    /// output fails.
+    pub fn element_string(&mut self, value: &str) -> Result<(), NbtError> {
+        self.element(Kind::String)?;
+        self.write_str(value)
+    }
+
+    /// Writes an entry of an owned value of any kind.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a compound,
+    /// a length is out of range, or the output fails.
+    pub fn field_value(&mut self, name: &str, value: &Value) -> Result<(), NbtError> {
+        self.field(name, value.kind())?;
+        write_payload(&mut self.writer, value, self.flavor)
+    }
+
+    /// Writes an element of an owned value of any kind.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a list of
+    /// the kind of the value, the list is already full, a length is out of
+    /// range, or the output fails.
+    pub fn element_value(&mut self, value: &Value) -> Result<(), NbtError> {
+        self.element(value.kind())?;
+        write_payload(&mut self.writer, value, self.flavor)
+    }
+
+    /// Begins an entry of [`Kind::Compound`], which is ended by
+    /// [`Writer::end_compound`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a compound,
+    /// the nesting is too deep, or the output fails.
+    pub fn begin_compound(&mut self, name: &str) -> Result<(), NbtError> {
+        self.enter()?;
+        self.field(name, Kind::Compound)?;
+        self.stack.push(Frame::Compound);
+        Ok(())
+    }
+
+    /// Begins an element of [`Kind::Compound`], which is ended by
+    /// [`Writer::end_compound`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a list of
+    /// compounds, the list is already full, the nesting is too deep, or the
+    /// output fails.
+    pub fn begin_compound_element(&mut self) -> Result<(), NbtError> {
+        self.enter()?;
+        self.element(Kind::Compound)?;
+        self.stack.push(Frame::Compound);
+        Ok(())
+    }
+
+    /// Ends the innermost compound.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a compound,
+    /// or the output fails.
+    pub fn end_compound(&mut self) -> Result<(), NbtError> {
+        match self.stack.last() {
+            Some(Frame::Compound) => {
+                self.stack.pop();
+                self.writer.write_all(&[0])
+            }
+            _ => Err(NbtError::InvalidState(
+                "end of compound outside of compound",
+            )),
+        }
+    }
+
+    /// Begins an entry of [`Kind::List`] of the element kind and length,
+    /// which is ended by [`Writer::end_list`] after exactly `len` elements.
+    /// The element kind [`None`] means *TAG_End*, which is only valid for an
+    /// empty list.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a compound,
+    /// the nesting is too deep, the length is out of range, a non-empty list
+    /// has no element kind, or the output fails.
+    pub fn begin_list(
+        &mut self,
+        name: &str,
+        kind: Option<Kind>,
+        len: usize,
+    ) -> Result<(), NbtError> {
+        self.enter_list(kind, len)?;
+        self.field(name, Kind::List)?;
+        self.write_list_header(kind, len)
+    }
+
+    /// Begins an element of [`Kind::List`] of the element kind and length,
+    /// which is ended by [`Writer::end_list`] after exactly `len` elements.
+    /// The element kind [`None`] means *TAG_End*, as in
+    /// [`Writer::begin_list`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a list of
+    /// lists, the list is already full, the nesting is too deep, the length
+    /// is out of range, a non-empty list has no element kind, or the output
+    /// fails.
+    pub fn begin_list_element(&mut self, kind: Option<Kind>, len: usize) -> Result<(), NbtError> {
+        self.enter_list(kind, len)?;
+        self.element(Kind::List)?;
+        self.write_list_header(kind, len)
+    }
+
+    /// Ends the innermost list.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the writer is not inside a list, or
+    /// the number of elements is not the declared length.
+    pub fn end_list(&mut self) -> Result<(), NbtError> {
+        match self.stack.last() {
+            Some(&Frame::List { len, count, .. }) if count == len => {
+                self.stack.pop();
+                Ok(())
+            }
+            Some(&Frame::List { len, count, .. }) => Err(NbtError::Message(format!(
+                "expected {len} elements, found {count}"
+            ))),
+            _ => Err(NbtError::InvalidState("end of list outside of list")),
+        }
+    }
+
+    fn enter(&self) -> Result<(), NbtError> {
+        if self.stack.len() >= NbtError::MAX_DEPTH {
+            return Err(NbtError::DepthLimit);
+        }
+        Ok(())
+    }
+
+    /// Writes the tag ID and the name of an entry, or a named root tag.
+    fn field(&mut self, name: &str, kind: Kind) -> Result<(), NbtError> {
+        match self.stack.last() {
+            Some(Frame::Compound) => {}
+            None if self.named => {}
+            _ => return Err(NbtError::InvalidState("entry outside of compound")),
+        }
+        self.writer.write_all(&[kind as u8])?;
+        self.write_str(name)
+    }
+
+    /// Counts an element of the innermost list, or writes the tag ID of a
+    /// nameless root tag.
+    fn element(&mut self, kind: Kind) -> Result<(), NbtError> {
+        match self.stack.last_mut() {
+            Some(Frame::List {
+                kind: expected,
+                len,
+                count,
+            }) => {
+                if count == len {
+                    return Err(NbtError::Message(format!(
+                        "expected {len} elements, found more"
+                    )));
+                }
+                match *expected {
+                    Some(expected) if expected != kind => {
+                        return Err(NbtError::UnexpectedKind {
+                            expected,
+                            found: Some(kind),
+                        });
+                    }
+                    _ => {}
+                }
+                *count += 1;
+                Ok(())
+            }
+            None if !self.named => self.writer.write_all(&[kind as u8]),
+            _ => Err(NbtError::InvalidState("element outside of list")),
+        }
+    }
+
+    /// Checks the beginning of a list before anything is written.
+    fn enter_list(&self, kind: Option<Kind>, len: usize) -> Result<(), NbtError> {
+        self.enter()?;
+        // A non-empty list always has an element kind.
+        if kind.is_none() && len > 0 {
+            return Err(NbtError::UnexpectedKind {
+                expected: Kind::List,
+                found: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn write_list_header(&mut self, kind: Option<Kind>, len: usize) -> Result<(), NbtError> {
+        self.writer
+            .write_all(&[kind.map_or(0, |kind| kind as u8)])?;
+        self.flavor.write_len(&mut self.writer, len)?;
+        self.stack.push(Frame::List {
+            kind,
+            len,
+            count: 0,
+        });
+        Ok(())
+    }
+
+    fn write_str(&mut self, string: &str) -> Result<(), NbtError> {
+        let bytes = self.flavor.encode_str(string);
+        self.flavor.write_str_len(&mut self.writer, bytes.len())?;
+        self.writer.write_all(&bytes)
+    }
+
+    fn write_array<T: Copy>(
+        &mut self,
+        array: &[T],
+        write: fn(F, &mut W, T) -> Result<(), NbtError>,
+    ) -> Result<(), NbtError> {
+        self.flavor.write_len(&mut self.writer, array.len())?;
+        for &element in array {
+            write(self.flavor, &mut self.writer, element)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_i8<F, W: Write + ?Sized>(_: F, output: &mut W, value: i8) -> Result<(), NbtError> {
+    output.write_all(&value.to_be_bytes())
+}
@@ -0,0 +1,272 @@
+//! The [`Compound`] type and its iterators.
+
+use alloc::vec::Vec;
+use core::{mem, slice};
+
+use crate::value::{ThinStr, Value};
+
+/// An unordered list of attribute-value pairs.
+///
+/// The entries are stored in a vector in insertion order, and looked up by a
+/// linear search, which is cheaper than hashing for the small compounds that
+/// are common in minecraft data. The keys are stored as [`ThinStr`]s.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Compound {
+    entries: Vec<(ThinStr, Value)>,
+}
+
+impl Compound {
+    /// Creates a new empty compound.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Compound {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Creates a new empty compound with at least the specified capacity.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Compound {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of entries in the compound.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the compound contains no entries.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the compound contains the given key.
+    #[inline]
+    #[must_use]
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns the value of the given key, or [`None`] if not present.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        let index = self.position(key)?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns the mutable value of the given key, or [`None`] if not present.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        let index = self.position(key)?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Inserts a key-value pair into the compound, returning the old value if
+    /// the key is already present.
+    pub fn insert<K>(&mut self, key: K, value: impl Into<Value>) -> Option<Value>
+    where
+        K: AsRef<str> + Into<ThinStr>,
+    {
+        let value = value.into();
+        match self.position(key.as_ref()) {
+            Some(index) => Some(mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.entries.push((key.into(), value));
+                None
+            }
+        }
+    }
+
+    /// Removes the given key from the compound, returning its value if it was
+    /// present.
+    ///
+    /// This preserves the order of the remaining entries.
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let index = self.position(key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Clears the compound, removing all entries.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Merges the entries of the source into the compound like
+    /// `CompoundTag.merge` of vanilla, returning `true` if the compound
+    /// changed.
+    ///
+    /// An entry that is a compound in both is merged recursively, and any
+    /// other entry of the source is inserted as a clone, replacing the value
+    /// of the same key if any. This is [`Compound::merge_with`] in
+    /// [`MergeMode::Merge`].
+    #[inline]
+    pub fn merge(&mut self, source: &Compound) -> bool {
+        self.merge_with(source, MergeMode::Merge)
+    }
+
+    /// Merges the entries of the source into the compound in the given mode,
+    /// returning `true` if the compound changed.
+    ///
+    /// The new entries are appended in the order of the source, and an entry
+    /// that is equal to the one of the source is left untouched.
+    pub fn merge_with(&mut self, source: &Compound, mode: MergeMode) -> bool {
+        let mut changed = false;
+        for (key, value) in source {
+            match (self.get_mut(key), value) {
+                (Some(Value::Compound(target)), Value::Compound(source))
+                    if mode == MergeMode::Merge =>
+                {
+                    changed |= target.merge_with(source, mode);
+                }
+                (Some(target), value) if target == value => {}
+                _ => {
+                    self.insert(key, value.clone());
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Returns an iterator over the entries in insertion order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.entries.iter())
+    }
+
+    /// Returns an iterator over the mutable entries in insertion order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut(self.entries.iter_mut())
+    }
+
+    /// Returns an iterator over the keys in insertion order.
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values in insertion order.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.entries.iter().position(|(k, _)| k.as_str() == key)
+    }
+}
+
+/// How [`Compound::merge_with`] merges an entry that is a compound in both
+/// the target and the source.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MergeMode {
+    /// The nested compounds are merged recursively, so that the entries only
+    /// in the target are kept at any depth, like the `/data merge` command.
+    #[default]
+    Merge,
+    /// The nested compound of the target is replaced as a whole by a clone of
+    /// the source, so that the entries of the source replace whole subtrees.
+    Replace,
+}
+
+impl<K: AsRef<str> + Into<ThinStr>, V: Into<Value>> FromIterator<(K, V)> for Compound {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut compound = Compound::new();
+        compound.extend(iter);
+        compound
+    }
+}
+
+impl<K: AsRef<str> + Into<ThinStr>, V: Into<Value>> Extend<(K, V)> for Compound {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Compound {
+    type Item = (&'a str, &'a Value);
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Compound {
+    type Item = (&'a str, &'a mut Value);
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl IntoIterator for Compound {
+    type Item = (ThinStr, Value);
+    type IntoIter = alloc::vec::IntoIter<(ThinStr, Value)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+/// An iterator over the entries of a [`Compound`].
+///
+/// This is created by [`Compound::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a>(slice::Iter<'a, (ThinStr, Value)>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// A mutable iterator over the entries of a [`Compound`].
+///
+/// This is created by [`Compound::iter_mut`].
+#[derive(Debug)]
+pub struct IterMut<'a>(slice::IterMut<'a, (ThinStr, Value)>);
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = (&'a str, &'a mut Value);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_> {}
@@ -0,0 +1,496 @@
+//! The [`List`] type and its iterators.
+
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::mem;
+
+use crate::kind::Kind;
+use crate::value::{ByteArray, Compound, IntArray, LongArray, ThinStr, Value, ValueRef};
+
+/// An ordered list of NBT values of the same kind.
+///
+/// The elements are stored in a vector typed by the element kind, so that the
+/// primitive elements are unboxed, and the kind is stored once for the whole
+/// list rather than once per element.
+///
+/// The element kind of [`List::End`] is [`None`], i.e., *TAG_End*, which is
+/// the kind of lists that are empty and not yet typed. An empty list can also
+/// have an element kind, e.g., `List::Int(Vec::new())`.
+///
+/// # Mixed lists
+///
+/// Since minecraft Java Edition 1.21.5, a list may contain elements of
+/// different kinds, which is [`List::Mixed`]. On the wire, it is still a list
+/// of [`Kind::Compound`], where each element is wrapped in a compound of a
+/// single entry with an empty key, e.g., `{"": 1}`, unless it is a compound
+/// that cannot be mistaken for such a wrapper. The binary codec wraps the
+/// elements when writing, and unwraps them when reading a list of compounds
+/// that contains any wrapper. See [`List::from_values`] and
+/// [`List::push_mixed`] to build one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum List {
+    /// An empty list without an element kind.
+    #[default]
+    End,
+    /// A list of [`Kind::Byte`].
+    Byte(Vec<i8>),
+    /// A list of [`Kind::Short`].
+    Short(Vec<i16>),
+    /// A list of [`Kind::Int`].
+    Int(Vec<i32>),
+    /// A list of [`Kind::Long`].
+    Long(Vec<i64>),
+    /// A list of [`Kind::Float`].
+    Float(Vec<f32>),
+    /// A list of [`Kind::Double`].
+    Double(Vec<f64>),
+    /// A list of [`Kind::ByteArray`].
+    ByteArray(Vec<ByteArray>),
+    /// A list of [`Kind::String`].
+    String(Vec<ThinStr>),
+    /// A list of [`Kind::List`].
+    List(Vec<List>),
+    /// A list of [`Kind::Compound`].
+    Compound(Vec<Compound>),
+    /// A list of [`Kind::IntArray`].
+    IntArray(Vec<IntArray>),
+    /// A list of [`Kind::LongArray`].
+    LongArray(Vec<LongArray>),
+    /// A list of elements of different kinds, which is written as a list of
+    /// [`Kind::Compound`], see [mixed lists](List#mixed-lists).
+    Mixed(Vec<Value>),
+}
+
+/// Applies the same expression to the typed vector of every non-end variant.
+macro_rules! each {
+    ($list:expr, $vec:ident => $expr:expr, End => $end:expr) => {
+        match $list {
+            List::End => $end,
+            List::Byte($vec) => $expr,
+            List::Short($vec) => $expr,
+            List::Int($vec) => $expr,
+            List::Long($vec) => $expr,
+            List::Float($vec) => $expr,
+            List::Double($vec) => $expr,
+            List::ByteArray($vec) => $expr,
+            List::String($vec) => $expr,
+            List::List($vec) => $expr,
+            List::Compound($vec) => $expr,
+            List::IntArray($vec) => $expr,
+            List::LongArray($vec) => $expr,
+            List::Mixed($vec) => $expr,
+        }
+    };
+}
+
+impl List {
+    /// Creates a new empty list without an element kind.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        List::End
+    }
+
+    /// Creates a new empty list of the given element kind.
+    #[must_use]
+    pub const fn with_kind(kind: Kind) -> Self {
+        match kind {
+            Kind::Byte => List::Byte(Vec::new()),
+            Kind::Short => List::Short(Vec::new()),
+            Kind::Int => List::Int(Vec::new()),
+            Kind::Long => List::Long(Vec::new()),
+            Kind::Float => List::Float(Vec::new()),
+            Kind::Double => List::Double(Vec::new()),
+            Kind::ByteArray => List::ByteArray(Vec::new()),
+            Kind::String => List::String(Vec::new()),
+            Kind::List => List::List(Vec::new()),
+            Kind::Compound => List::Compound(Vec::new()),
+            Kind::IntArray => List::IntArray(Vec::new()),
+            Kind::LongArray => List::LongArray(Vec::new()),
+        }
+    }
+
+    /// Returns the kind of the elements, or [`None`] for [`List::End`].
+    ///
+    /// This is the element kind on the wire, i.e., [`Kind::Compound`] for
+    /// [`List::Mixed`].
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Option<Kind> {
+        Some(match self {
+            List::End => return None,
+            List::Byte(_) => Kind::Byte,
+            List::Short(_) => Kind::Short,
+            List::Int(_) => Kind::Int,
+            List::Long(_) => Kind::Long,
+            List::Float(_) => Kind::Float,
+            List::Double(_) => Kind::Double,
+            List::ByteArray(_) => Kind::ByteArray,
+            List::String(_) => Kind::String,
+            List::List(_) => Kind::List,
+            List::Compound(_) => Kind::Compound,
+            List::IntArray(_) => Kind::IntArray,
+            List::LongArray(_) => Kind::LongArray,
+            List::Mixed(_) => Kind::Compound,
+        })
+    }
+
+    /// Returns the number of elements in the list.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        each!(self, vec => vec.len(), End => 0)
+    }
+
+    /// Returns `true` if the list contains no elements.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the element at the given index, or [`None`] if out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<ValueRef<'_>> {
+        Some(match self {
+            List::End => return None,
+            List::Byte(vec) => ValueRef::Byte(*vec.get(index)?),
+            List::Short(vec) => ValueRef::Short(*vec.get(index)?),
+            List::Int(vec) => ValueRef::Int(*vec.get(index)?),
+            List::Long(vec) => ValueRef::Long(*vec.get(index)?),
+            List::Float(vec) => ValueRef::Float(*vec.get(index)?),
+            List::Double(vec) => ValueRef::Double(*vec.get(index)?),
+            List::ByteArray(vec) => ValueRef::ByteArray(vec.get(index)?),
+            List::String(vec) => ValueRef::String(vec.get(index)?),
+            List::List(vec) => ValueRef::List(vec.get(index)?),
+            List::Compound(vec) => ValueRef::Compound(vec.get(index)?),
+            List::IntArray(vec) => ValueRef::IntArray(vec.get(index)?),
+            List::LongArray(vec) => ValueRef::LongArray(vec.get(index)?),
+            List::Mixed(vec) => vec.get(index)?.as_borrowed(),
+        })
+    }
+
+    /// Appends an element to the back of the list.
+    ///
+    /// Pushing into [`List::End`] turns it into a list of the element kind,
+    /// and [`List::Mixed`] accepts elements of any kind.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the value back if its kind differs from the kind
+    /// of the list, see [`List::push_mixed`] otherwise.
+    pub fn push(&mut self, value: impl Into<Value>) -> Result<(), Value> {
+        let value = value.into();
+        if let List::End = self {
+            *self = List::with_kind(value.kind());
+        }
+        match (self, value) {
+            (List::Byte(vec), Value::Byte(value)) => vec.push(value),
+            (List::Short(vec), Value::Short(value)) => vec.push(value),
+            (List::Int(vec), Value::Int(value)) => vec.push(value),
+            (List::Long(vec), Value::Long(value)) => vec.push(value),
+            (List::Float(vec), Value::Float(value)) => vec.push(value),
+            (List::Double(vec), Value::Double(value)) => vec.push(value),
+            (List::ByteArray(vec), Value::ByteArray(value)) => vec.push(value),
+            (List::String(vec), Value::String(value)) => vec.push(value),
+            (List::List(vec), Value::List(value)) => vec.push(*value),
+            (List::Compound(vec), Value::Compound(value)) => vec.push(*value),
+            (List::IntArray(vec), Value::IntArray(value)) => vec.push(value),
+            (List::LongArray(vec), Value::LongArray(value)) => vec.push(value),
+            (List::Mixed(vec), value) => vec.push(value),
+            (_, value) => return Err(value),
+        }
+        Ok(())
+    }
+
+    /// Removes the last element from the list and returns it, or [`None`] if
+    /// the list is empty.
+    ///
+    /// The element kind is kept even if the list becomes empty.
+    pub fn pop(&mut self) -> Option<Value> {
+        Some(match self {
+            List::End => return None,
+            List::Byte(vec) => Value::from(vec.pop()?),
+            List::Short(vec) => Value::from(vec.pop()?),
+            List::Int(vec) => Value::from(vec.pop()?),
+            List::Long(vec) => Value::from(vec.pop()?),
+            List::Float(vec) => Value::from(vec.pop()?),
+            List::Double(vec) => Value::from(vec.pop()?),
+            List::ByteArray(vec) => Value::from(vec.pop()?),
+            List::String(vec) => Value::from(vec.pop()?),
+            List::List(vec) => Value::from(vec.pop()?),
+            List::Compound(vec) => Value::from(vec.pop()?),
+            List::IntArray(vec) => Value::from(vec.pop()?),
+            List::LongArray(vec) => Value::from(vec.pop()?),
+            List::Mixed(vec) => vec.pop()?,
+        })
+    }
+
+    /// Replaces the element at the given index, returning the old element.
+    ///
+    /// # Errors
+    ///
+    /// This function returns the value back if the index is out of bounds, or
+    /// its kind differs from the kind of the list.
+    pub fn set(&mut self, index: usize, value: impl Into<Value>) -> Result<Value, Value> {
+        let value = value.into();
+        if index >= self.len() {
+            return Err(value);
+        }
+        Ok(match (self, value) {
+            (List::Byte(vec), Value::Byte(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Short(vec), Value::Short(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Int(vec), Value::Int(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Long(vec), Value::Long(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Float(vec), Value::Float(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Double(vec), Value::Double(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::ByteArray(vec), Value::ByteArray(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::String(vec), Value::String(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::List(vec), Value::List(value)) => {
+                Value::from(mem::replace(&mut vec[index], *value))
+            }
+            (List::Compound(vec), Value::Compound(value)) => {
+                Value::from(mem::replace(&mut vec[index], *value))
+            }
+            (List::IntArray(vec), Value::IntArray(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::LongArray(vec), Value::LongArray(value)) => {
+                Value::from(mem::replace(&mut vec[index], value))
+            }
+            (List::Mixed(vec), value) => mem::replace(&mut vec[index], value),
+            (_, value) => return Err(value),
+        })
+    }
+
+    /// Removes the element at the given index and returns it, shifting the
+    /// elements after it, or returns [`None`] if out of bounds.
+    ///
+    /// The element kind is kept even if the list becomes empty.
+    pub fn remove(&mut self, index: usize) -> Option<Value> {
+        fn take<T: Into<Value>>(vec: &mut Vec<T>, index: usize) -> Value {
+            vec.remove(index).into()
+        }
+
+        if index >= self.len() {
+            return None;
+        }
+        Some(each!(self, vec => take(vec, index), End => return None))
+    }
+
+    /// Appends an element to the back of the list, turning the list into
+    /// [`List::Mixed`] if the kind of the element differs.
+    pub fn push_mixed(&mut self, value: impl Into<Value>) {
+        if let Err(value) = self.push(value) {
+            let mut vec: Vec<Value> = mem::take(self).into_iter().collect();
+            vec.push(value);
+            *self = List::Mixed(vec);
+        }
+    }
+
+    /// Returns `true` if the list is [`List::Mixed`].
+    #[inline]
+    #[must_use]
+    pub const fn is_mixed(&self) -> bool {
+        matches!(self, List::Mixed(_))
+    }
+
+    /// Clears the list, removing all elements and the element kind.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = List::End;
+    }
+
+    /// Creates a list from the elements, which is a typed list if they are of
+    /// the same kind, or [`List::Mixed`] otherwise.
+    #[must_use]
+    pub fn from_values(elements: Vec<Value>) -> Self {
+        match elements.first() {
+            Some(first) if elements.iter().any(|value| value.kind() != first.kind()) => {
+                List::Mixed(elements)
+            }
+            // The elements are of the same kind.
+            _ => List::try_from(elements).unwrap_or_default(),
+        }
+    }
+
+    /// Returns an iterator over the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            list: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($ty:ty)),* $(,)?) => {$(
+        impl From<Vec<$ty>> for List {
+            #[inline]
+            fn from(vec: Vec<$ty>) -> Self {
+                List::$variant(vec)
+            }
+        }
+    )*};
+}
+
+impl_from! {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(ByteArray),
+    String(ThinStr),
+    List(List),
+    Compound(Compound),
+    IntArray(IntArray),
+    LongArray(LongArray),
+}
+
+impl TryFrom<Vec<Value>> for List {
+    type Error = Value;
+
+    /// Converts from the elements into a list, returning the first element of
+    /// a different kind if they are not of the same kind.
+    fn try_from(elements: Vec<Value>) -> Result<Self, Self::Error> {
+        let mut list = match elements.first() {
+            Some(value) => List::with_kind(value.kind()),
+            None => List::End,
+        };
+        each!(&mut list, vec => vec.reserve(elements.len()), End => ());
+        for value in elements {
+            list.push(value)?;
+        }
+        Ok(list)
+    }
+}
+
+/// Returns `true` if the compound is a wrapper of an element of a mixed list,
+/// i.e., it has a single entry with an empty key.
+pub(crate) fn is_wrapper(compound: &Compound) -> bool {
+    compound.len() == 1 && compound.contains_key("")
+}
+
+/// Unwraps an element of a mixed list.
+pub(crate) fn unwrap(mut compound: Compound) -> Value {
+    match compound.len() {
+        1 => match compound.remove("") {
+            Some(value) => value,
+            None => Value::from(compound),
+        },
+        _ => Value::from(compound),
+    }
+}
+
+impl<'a> IntoIterator for &'a List {
+    type Item = ValueRef<'a>;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for List {
+    type Item = Value;
+    type IntoIter = IntoIter;
+
+    #[inline]
+    fn into_iter(mut self) -> Self::IntoIter {
+        // Reverse the elements so that they can be popped from the back.
+        each!(&mut self, vec => vec.reverse(), End => ());
+        IntoIter(self)
+    }
+}
+
+/// An iterator over the elements of a [`List`].
+///
+/// This is created by [`List::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    list: &'a List,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = ValueRef<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            self.list.get(self.front - 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            self.list.get(self.back)
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// An owning iterator over the elements of a [`List`].
+///
+/// This is created by [`List::into_iter`].
+#[derive(Clone, Debug)]
+pub struct IntoIter(List);
+
+impl Iterator for IntoIter {
+    type Item = Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl FusedIterator for IntoIter {}
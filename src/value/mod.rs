@@ -0,0 +1,401 @@
+//! This module provides the owned NBT value tree [`Value`], together with the
+//! two container types [`List`] and [`Compound`].
+//!
+//! Each variant of [`Value`] corresponds to one variant of [`Kind`], which can
+//! be obtained by [`Value::kind`].
+//!
+//! # Memory layout
+//!
+//! The value tree is designed to be compact:
+//!
+//! - [`Value`] is only 16 bytes on 64-bit targets, i.e., one tag byte and an
+//!   8-byte payload. All the heap variants are thin pointers, see [`ThinSlice`]
+//!   and [`ThinStr`], and the kind is stored only once as the enum tag.
+//! - [`List`] stores the elements in a typed vector, so the primitive elements
+//!   are unboxed and the kind is stored once for the whole list.
+//! - [`Compound`] stores the entries in a vector rather than a hash map.
+
+pub mod compound;
+pub mod list;
+mod thin;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::kind::Kind;
+
+pub use self::compound::{Compound, MergeMode};
+pub use self::list::List;
+pub use self::thin::{ThinSlice, ThinStr};
+
+/// The payload of [`Value::ByteArray`].
+pub type ByteArray = ThinSlice<i8>;
+
+/// The payload of [`Value::IntArray`].
+pub type IntArray = ThinSlice<i32>;
+
+/// The payload of [`Value::LongArray`].
+pub type LongArray = ThinSlice<i64>;
+
+/// An owned NBT value.
+///
+/// There is exactly one variant for each [`Kind`], holding the payload of the
+/// corresponding tag. See the [module documentation](self) for the memory
+/// layout.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// See [`Kind::Byte`].
+    Byte(i8),
+    /// See [`Kind::Short`].
+    Short(i16),
+    /// See [`Kind::Int`].
+    Int(i32),
+    /// See [`Kind::Long`].
+    Long(i64),
+    /// See [`Kind::Float`].
+    Float(f32),
+    /// See [`Kind::Double`].
+    Double(f64),
+    /// See [`Kind::ByteArray`].
+    ByteArray(ByteArray),
+    /// See [`Kind::String`].
+    String(ThinStr),
+    /// See [`Kind::List`].
+    List(Box<List>),
+    /// See [`Kind::Compound`].
+    Compound(Box<Compound>),
+    /// See [`Kind::IntArray`].
+    IntArray(IntArray),
+    /// See [`Kind::LongArray`].
+    LongArray(LongArray),
+}
+
+impl Value {
+    /// Creates the default value of the given kind, i.e., zero for numeric
+    /// kinds and empty for the others.
+    #[must_use]
+    pub fn new(kind: Kind) -> Self {
+        match kind {
+            Kind::Byte => Value::Byte(0),
+            Kind::Short => Value::Short(0),
+            Kind::Int => Value::Int(0),
+            Kind::Long => Value::Long(0),
+            Kind::Float => Value::Float(0.0),
+            Kind::Double => Value::Double(0.0),
+            Kind::ByteArray => Value::ByteArray(ThinSlice::new()),
+            Kind::String => Value::String(ThinStr::new()),
+            Kind::List => Value::List(Box::default()),
+            Kind::Compound => Value::Compound(Box::default()),
+            Kind::IntArray => Value::IntArray(ThinSlice::new()),
+            Kind::LongArray => Value::LongArray(ThinSlice::new()),
+        }
+    }
+
+    /// Returns the kind of the value.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            Value::Byte(_) => Kind::Byte,
+            Value::Short(_) => Kind::Short,
+            Value::Int(_) => Kind::Int,
+            Value::Long(_) => Kind::Long,
+            Value::Float(_) => Kind::Float,
+            Value::Double(_) => Kind::Double,
+            Value::ByteArray(_) => Kind::ByteArray,
+            Value::String(_) => Kind::String,
+            Value::List(_) => Kind::List,
+            Value::Compound(_) => Kind::Compound,
+            Value::IntArray(_) => Kind::IntArray,
+            Value::LongArray(_) => Kind::LongArray,
+        }
+    }
+
+    /// Returns a borrowed view of the value.
+    #[must_use]
+    pub fn as_borrowed(&self) -> ValueRef<'_> {
+        match self {
+            Value::Byte(value) => ValueRef::Byte(*value),
+            Value::Short(value) => ValueRef::Short(*value),
+            Value::Int(value) => ValueRef::Int(*value),
+            Value::Long(value) => ValueRef::Long(*value),
+            Value::Float(value) => ValueRef::Float(*value),
+            Value::Double(value) => ValueRef::Double(*value),
+            Value::ByteArray(value) => ValueRef::ByteArray(value),
+            Value::String(value) => ValueRef::String(value),
+            Value::List(value) => ValueRef::List(value),
+            Value::Compound(value) => ValueRef::Compound(value),
+            Value::IntArray(value) => ValueRef::IntArray(value),
+            Value::LongArray(value) => ValueRef::LongArray(value),
+        }
+    }
+
+    /// Returns the byte if the value is a [`Kind::Byte`].
+    #[inline]
+    #[must_use]
+    pub const fn as_byte(&self) -> Option<i8> {
+        match *self {
+            Value::Byte(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if the value is a [`Kind::Byte`], where any
+    /// non-zero byte is regarded as `true`.
+    #[inline]
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Byte(value) => Some(value != 0),
+            _ => None,
+        }
+    }
+
+    /// Returns the short if the value is a [`Kind::Short`].
+    #[inline]
+    #[must_use]
+    pub const fn as_short(&self) -> Option<i16> {
+        match *self {
+            Value::Short(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the int if the value is a [`Kind::Int`].
+    #[inline]
+    #[must_use]
+    pub const fn as_int(&self) -> Option<i32> {
+        match *self {
+            Value::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the long if the value is a [`Kind::Long`].
+    #[inline]
+    #[must_use]
+    pub const fn as_long(&self) -> Option<i64> {
+        match *self {
+            Value::Long(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the float if the value is a [`Kind::Float`].
+    #[inline]
+    #[must_use]
+    pub const fn as_float(&self) -> Option<f32> {
+        match *self {
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the double if the value is a [`Kind::Double`].
+    #[inline]
+    #[must_use]
+    pub const fn as_double(&self) -> Option<f64> {
+        match *self {
+            Value::Double(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes if the value is a [`Kind::ByteArray`].
+    #[inline]
+    #[must_use]
+    pub fn as_byte_array(&self) -> Option<&[i8]> {
+        match self {
+            Value::ByteArray(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is a [`Kind::String`].
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the list if the value is a [`Kind::List`].
+    #[inline]
+    #[must_use]
+    pub fn as_list(&self) -> Option<&List> {
+        match self {
+            Value::List(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable list if the value is a [`Kind::List`].
+    #[inline]
+    #[must_use]
+    pub fn as_list_mut(&mut self) -> Option<&mut List> {
+        match self {
+            Value::List(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the compound if the value is a [`Kind::Compound`].
+    #[inline]
+    #[must_use]
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Value::Compound(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable compound if the value is a [`Kind::Compound`].
+    #[inline]
+    #[must_use]
+    pub fn as_compound_mut(&mut self) -> Option<&mut Compound> {
+        match self {
+            Value::Compound(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the ints if the value is a [`Kind::IntArray`].
+    #[inline]
+    #[must_use]
+    pub fn as_int_array(&self) -> Option<&[i32]> {
+        match self {
+            Value::IntArray(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the longs if the value is a [`Kind::LongArray`].
+    #[inline]
+    #[must_use]
+    pub fn as_long_array(&self) -> Option<&[i64]> {
+        match self {
+            Value::LongArray(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// A borrowed view of an NBT value.
+///
+/// This is mostly used to access the elements of a [`List`], which are not
+/// stored as [`Value`]s, see [`List::get`] and [`List::iter`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueRef<'a> {
+    /// See [`Kind::Byte`].
+    Byte(i8),
+    /// See [`Kind::Short`].
+    Short(i16),
+    /// See [`Kind::Int`].
+    Int(i32),
+    /// See [`Kind::Long`].
+    Long(i64),
+    /// See [`Kind::Float`].
+    Float(f32),
+    /// See [`Kind::Double`].
+    Double(f64),
+    /// See [`Kind::ByteArray`].
+    ByteArray(&'a [i8]),
+    /// See [`Kind::String`].
+    String(&'a str),
+    /// See [`Kind::List`].
+    List(&'a List),
+    /// See [`Kind::Compound`].
+    Compound(&'a Compound),
+    /// See [`Kind::IntArray`].
+    IntArray(&'a [i32]),
+    /// See [`Kind::LongArray`].
+    LongArray(&'a [i64]),
+}
+
+impl ValueRef<'_> {
+    /// Returns the kind of the value.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            ValueRef::Byte(_) => Kind::Byte,
+            ValueRef::Short(_) => Kind::Short,
+            ValueRef::Int(_) => Kind::Int,
+            ValueRef::Long(_) => Kind::Long,
+            ValueRef::Float(_) => Kind::Float,
+            ValueRef::Double(_) => Kind::Double,
+            ValueRef::ByteArray(_) => Kind::ByteArray,
+            ValueRef::String(_) => Kind::String,
+            ValueRef::List(_) => Kind::List,
+            ValueRef::Compound(_) => Kind::Compound,
+            ValueRef::IntArray(_) => Kind::IntArray,
+            ValueRef::LongArray(_) => Kind::LongArray,
+        }
+    }
+
+    /// Clones the borrowed data into an owned value.
+    #[must_use]
+    pub fn to_owned(&self) -> Value {
+        match *self {
+            ValueRef::Byte(value) => Value::Byte(value),
+            ValueRef::Short(value) => Value::Short(value),
+            ValueRef::Int(value) => Value::Int(value),
+            ValueRef::Long(value) => Value::Long(value),
+            ValueRef::Float(value) => Value::Float(value),
+            ValueRef::Double(value) => Value::Double(value),
+            ValueRef::ByteArray(value) => Value::from(value),
+            ValueRef::String(value) => Value::from(value),
+            ValueRef::List(value) => Value::from(value.clone()),
+            ValueRef::Compound(value) => Value::from(value.clone()),
+            ValueRef::IntArray(value) => Value::from(value),
+            ValueRef::LongArray(value) => Value::from(value),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    #[inline]
+    fn from(value: &'a Value) -> Self {
+        value.as_borrowed()
+    }
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($ty:ty)),* $(,)?) => {$(
+        impl From<$ty> for Value {
+            #[inline]
+            fn from(value: $ty) -> Self {
+                Value::$variant(value.into())
+            }
+        }
+    )*};
+}
+
+impl_from! {
+    Byte(i8),
+    Byte(bool),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(ByteArray),
+    ByteArray(Vec<i8>),
+    ByteArray(&[i8]),
+    String(ThinStr),
+    String(String),
+    String(&str),
+    List(Box<List>),
+    List(List),
+    Compound(Box<Compound>),
+    Compound(Compound),
+    IntArray(IntArray),
+    IntArray(Vec<i32>),
+    IntArray(&[i32]),
+    LongArray(LongArray),
+    LongArray(Vec<i64>),
+    LongArray(&[i64]),
+}
@@ -0,0 +1,391 @@
+//! Thin owned slices, which store the length in the heap allocation so that
+//! the handle is only one pointer wide.
+
+use alloc::alloc::{Layout, alloc, dealloc, handle_alloc_error};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::{slice, str};
+
+/// The header of the allocation of [`ThinSlice`], followed by the elements.
+///
+/// The alignment is forced to 8, so that the elements (at most 8-byte aligned)
+/// can be placed right after the header without padding.
+#[repr(C, align(8))]
+struct Header {
+    len: usize,
+}
+
+/// The shared header of all empty slices, which is never deallocated.
+static EMPTY: Header = Header { len: 0 };
+
+/// An owned, immutable-length slice that is only one pointer wide.
+///
+/// This is the payload of [`Value::ByteArray`], [`Value::IntArray`] and
+/// [`Value::LongArray`]. Unlike [`Box<[T]>`](alloc::boxed::Box), which is a
+/// fat pointer, the length is stored in the heap allocation.
+///
+/// The elements can still be modified in place through [`DerefMut`]. To
+/// change the length, convert it into a [`Vec`] and back.
+///
+/// [`Value::ByteArray`]: crate::value::Value::ByteArray
+/// [`Value::IntArray`]: crate::value::Value::IntArray
+/// [`Value::LongArray`]: crate::value::Value::LongArray
+pub struct ThinSlice<T: Copy> {
+    ptr: NonNull<Header>,
+    marker: PhantomData<T>,
+}
+
+// SAFETY: `ThinSlice<T>` uniquely owns its elements, just like `Box<[T]>`.
+unsafe impl<T: Copy + Send> Send for ThinSlice<T> {}
+
+// SAFETY: `ThinSlice<T>` uniquely owns its elements, just like `Box<[T]>`.
+unsafe impl<T: Copy + Sync> Sync for ThinSlice<T> {}
+
+impl<T: Copy> ThinSlice<T> {
+    /// Evaluated at compile time to reject over-aligned element types.
+    const ALIGN_CHECK: () = assert!(align_of::<T>() <= align_of::<Header>());
+
+    /// Creates a new empty slice, which does not allocate.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        ThinSlice {
+            // SAFETY: The pointer to a static is never null. And the shared
+            // header is never written through it, since `as_mut_slice`
+            // returns an empty slice without touching it.
+            ptr: unsafe { NonNull::new_unchecked((&raw const EMPTY).cast_mut()) },
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a new slice by copying the given elements.
+    #[must_use]
+    pub fn from_slice(elements: &[T]) -> Self {
+        let () = Self::ALIGN_CHECK;
+        if elements.is_empty() {
+            return ThinSlice::new();
+        }
+
+        let layout = Self::layout(elements.len());
+        // SAFETY: The layout has a non-zero size since it contains a header.
+        let ptr = unsafe { alloc(layout) }.cast::<Header>();
+        let Some(ptr) = NonNull::new(ptr) else {
+            handle_alloc_error(layout);
+        };
+        // SAFETY: The allocation is valid for the header followed by
+        // `elements.len()` elements, and `T: Copy` requires no drop.
+        unsafe {
+            ptr.write(Header {
+                len: elements.len(),
+            });
+            let data = ptr.add(1).cast::<T>();
+            ptr::copy_nonoverlapping(elements.as_ptr(), data.as_ptr(), elements.len());
+        }
+        ThinSlice {
+            ptr,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        // SAFETY: The pointer always points to an initialized header.
+        unsafe { self.ptr.as_ref().len }
+    }
+
+    /// Returns `true` if the slice contains no elements.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Extracts a slice containing the elements.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        let len = self.len();
+        if len == 0 {
+            return &[];
+        }
+        // SAFETY: A non-empty slice owns an allocation of the header followed
+        // by `len` initialized elements.
+        unsafe { slice::from_raw_parts(self.ptr.add(1).cast::<T>().as_ptr(), len) }
+    }
+
+    /// Extracts a mutable slice containing the elements.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.len();
+        if len == 0 {
+            return &mut [];
+        }
+        // SAFETY: A non-empty slice uniquely owns an allocation of the header
+        // followed by `len` initialized elements.
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(1).cast::<T>().as_ptr(), len) }
+    }
+
+    /// Copies the elements into a new [`Vec`].
+    #[inline]
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.as_slice().to_vec()
+    }
+
+    fn layout(len: usize) -> Layout {
+        Layout::new::<Header>()
+            .extend(Layout::array::<T>(len).expect("capacity overflow"))
+            .expect("capacity overflow")
+            .0
+    }
+}
+
+impl<T: Copy> Drop for ThinSlice<T> {
+    fn drop(&mut self) {
+        let len = self.len();
+        if len != 0 {
+            // SAFETY: A non-empty slice owns an allocation created by `alloc`
+            // with the same layout.
+            unsafe { dealloc(self.ptr.as_ptr().cast(), Self::layout(len)) }
+        }
+    }
+}
+
+impl<T: Copy> Default for ThinSlice<T> {
+    #[inline]
+    fn default() -> Self {
+        ThinSlice::new()
+    }
+}
+
+impl<T: Copy> Clone for ThinSlice<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ThinSlice::from_slice(self)
+    }
+}
+
+impl<T: Copy> Deref for ThinSlice<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Copy> DerefMut for ThinSlice<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Copy> AsRef<[T]> for ThinSlice<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Copy> Borrow<[T]> for ThinSlice<T> {
+    #[inline]
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Copy + Debug> Debug for ThinSlice<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: Copy + PartialEq> PartialEq for ThinSlice<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Copy + Eq> Eq for ThinSlice<T> {}
+
+impl<T: Copy + PartialOrd> PartialOrd for ThinSlice<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Copy + Ord> Ord for ThinSlice<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Copy + Hash> Hash for ThinSlice<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<T: Copy> From<&[T]> for ThinSlice<T> {
+    #[inline]
+    fn from(elements: &[T]) -> Self {
+        ThinSlice::from_slice(elements)
+    }
+}
+
+impl<T: Copy> From<Vec<T>> for ThinSlice<T> {
+    #[inline]
+    fn from(elements: Vec<T>) -> Self {
+        ThinSlice::from_slice(&elements)
+    }
+}
+
+impl<T: Copy, const N: usize> From<[T; N]> for ThinSlice<T> {
+    #[inline]
+    fn from(elements: [T; N]) -> Self {
+        ThinSlice::from_slice(&elements)
+    }
+}
+
+impl<T: Copy> From<ThinSlice<T>> for Vec<T> {
+    #[inline]
+    fn from(elements: ThinSlice<T>) -> Self {
+        elements.to_vec()
+    }
+}
+
+impl<T: Copy> FromIterator<T> for ThinSlice<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        ThinSlice::from_slice(&iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a, T: Copy> IntoIterator for &'a ThinSlice<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// An owned, immutable string that is only one pointer wide.
+///
+/// This is the payload of [`Value::String`] and the key type of
+/// [`Compound`]. See [`ThinSlice`] for the memory layout.
+///
+/// [`Value::String`]: crate::value::Value::String
+/// [`Compound`]: crate::value::Compound
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThinStr(ThinSlice<u8>);
+
+impl ThinStr {
+    /// Creates a new empty string, which does not allocate.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        ThinStr(ThinSlice::new())
+    }
+
+    /// Extracts a string slice.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: The bytes are always copied from a valid UTF-8 string.
+        unsafe { str::from_utf8_unchecked(self.0.as_slice()) }
+    }
+}
+
+impl Deref for ThinStr {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ThinStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for ThinStr {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl Debug for ThinStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for ThinStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<str> for ThinStr {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ThinStr {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl From<&str> for ThinStr {
+    #[inline]
+    fn from(string: &str) -> Self {
+        ThinStr(ThinSlice::from_slice(string.as_bytes()))
+    }
+}
+
+impl From<String> for ThinStr {
+    #[inline]
+    fn from(string: String) -> Self {
+        ThinStr::from(string.as_str())
+    }
+}
+
+impl From<&String> for ThinStr {
+    #[inline]
+    fn from(string: &String) -> Self {
+        ThinStr::from(string.as_str())
+    }
+}
+
+impl From<ThinStr> for String {
+    #[inline]
+    fn from(string: ThinStr) -> Self {
+        String::from(string.as_str())
+    }
+}
@@ -0,0 +1,175 @@
+//! The [`ArrayView`] type and its iterator.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::binary::take;
+use crate::error::NbtError;
+use crate::view::read_len;
+
+/// A number that is an element of an [`ArrayView`], i.e., `i32` or `i64`.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Element: Copy + private::Sealed {
+    /// The size of the number in bytes.
+    const SIZE: usize;
+
+    /// Decodes the number from big-endian bytes of [`Element::SIZE`].
+    fn from_be_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_element {
+    ($($ty:ty),*) => {$(
+        impl Element for $ty {
+            const SIZE: usize = size_of::<$ty>();
+
+            #[inline]
+            fn from_be_slice(bytes: &[u8]) -> Self {
+                let mut array = [0; size_of::<$ty>()];
+                array.copy_from_slice(bytes);
+                <$ty>::from_be_bytes(array)
+            }
+        }
+    )*};
+}
+
+impl_element!(i32, i64);
+
+/// A zero-copy view of an int or long array, borrowing the unaligned
+/// big-endian bytes of the binary input.
+///
+/// The elements are decoded on access.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArrayView<'a, T> {
+    bytes: &'a [u8],
+    marker: PhantomData<T>,
+}
+
+impl<'a, T: Element> ArrayView<'a, T> {
+    /// Reads the payload of an array.
+    pub(crate) fn read(input: &mut &'a [u8]) -> Result<Self, NbtError> {
+        let len = read_len(input)?;
+        let total = len.checked_mul(T::SIZE).ok_or(NbtError::UnexpectedEof)?;
+        Ok(ArrayView {
+            bytes: take(input, total)?,
+            marker: PhantomData,
+        })
+    }
+
+    /// Returns the raw big-endian bytes of the elements, without the length
+    /// prefix.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the number of elements in the array.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+
+    /// Returns `true` if the array contains no elements.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the element at the given index, or [`None`] if out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let start = index * T::SIZE;
+        Some(T::from_be_slice(&self.bytes[start..start + T::SIZE]))
+    }
+
+    /// Returns an iterator over the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T> {
+        Iter {
+            chunks: self.bytes.chunks_exact(T::SIZE),
+            marker: PhantomData,
+        }
+    }
+
+    /// Copies the elements into a new [`Vec`].
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+impl<T: Element + Debug> Debug for ArrayView<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Element> IntoIterator for ArrayView<'a, T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Element> IntoIterator for &ArrayView<'a, T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an [`ArrayView`].
+///
+/// This is created by [`ArrayView::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T> {
+    chunks: core::slice::ChunksExact<'a, u8>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Element> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(T::from_be_slice)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<T: Element> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.chunks.next_back().map(T::from_be_slice)
+    }
+}
+
+impl<T: Element> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T: Element> FusedIterator for Iter<'_, T> {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
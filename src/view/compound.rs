@@ -0,0 +1,134 @@
+//! The [`CompoundView`] type and its iterator.
+
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::Compound;
+use crate::view::seek::skip_payload;
+use crate::view::{StrView, View, consumed, read_str};
+
+/// A zero-copy view of a compound, borrowing the binary input.
+///
+/// The entries are decoded on access, and looked up by a linear scan over the
+/// input, which skips the payloads of the other entries.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CompoundView<'a> {
+    /// The entries followed by *TAG_End*.
+    bytes: &'a [u8],
+}
+
+impl<'a> CompoundView<'a> {
+    /// Reads the payload of a compound, validating the nested tags.
+    pub(crate) fn read(input: &mut &'a [u8], depth: usize) -> Result<Self, NbtError> {
+        let start = *input;
+        skip_payload(Kind::Compound, input, depth)?;
+        Ok(CompoundView {
+            bytes: consumed(start, input),
+        })
+    }
+
+    /// Returns the raw bytes of the payload, i.e., the entries followed by
+    /// *TAG_End*.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the number of entries in the compound.
+    ///
+    /// This scans all the entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns `true` if the compound contains no entries.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.len() <= 1
+    }
+
+    /// Returns the value of the first entry of the given key, or [`None`] if
+    /// not present.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<View<'a>> {
+        self.iter()
+            .find_map(|(name, value)| (name == key).then_some(value))
+    }
+
+    /// Returns an iterator over the entries in the order of the input.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a> {
+        Iter { input: self.bytes }
+    }
+
+    /// Decodes the view into an owned compound.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a string is not well-formed MUTF-8.
+    pub fn to_owned(&self) -> Result<Compound, NbtError> {
+        let mut compound = Compound::new();
+        for (name, value) in self {
+            compound.insert(&*name.to_str()?, value.to_owned()?);
+        }
+        Ok(compound)
+    }
+}
+
+impl Debug for CompoundView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for CompoundView<'a> {
+    type Item = (StrView<'a>, View<'a>);
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &CompoundView<'a> {
+    type Item = (StrView<'a>, View<'a>);
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`CompoundView`].
+///
+/// This is created by [`CompoundView::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (StrView<'a>, View<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&id, rest) = self.input.split_first()?;
+        if id == 0 {
+            self.input = &[];
+            return None;
+        }
+        self.input = rest;
+        // The entries are validated when the view is read.
+        let kind = Kind::new(id).ok()?;
+        let name = read_str(&mut self.input).ok()?;
+        Some((name, View::read_valid(kind, &mut self.input)))
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
@@ -0,0 +1,188 @@
+//! The [`ListView`] type and its iterator.
+
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::List;
+use crate::value::list::{is_wrapper, unwrap};
+use crate::view::seek::skip_payload;
+use crate::view::{View, consumed, read_kind, read_len};
+
+/// A zero-copy view of a list, borrowing the binary input.
+///
+/// The elements are decoded on access. An element of a fixed-size kind, i.e.,
+/// a number, is accessed by its index in constant time, whereas the others
+/// are found by a linear scan.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ListView<'a> {
+    kind: Option<Kind>,
+    len: usize,
+    /// The payloads of the elements.
+    bytes: &'a [u8],
+}
+
+impl<'a> ListView<'a> {
+    /// Reads the payload of a list, validating the nested tags.
+    pub(crate) fn read(input: &mut &'a [u8], depth: usize) -> Result<Self, NbtError> {
+        let mut header = *input;
+        skip_payload(Kind::List, input, depth)?;
+        // The header is validated by skipping.
+        let kind = read_kind(&mut header)?;
+        let len = read_len(&mut header)?;
+        Ok(ListView {
+            kind,
+            len,
+            bytes: consumed(header, input),
+        })
+    }
+
+    /// Returns the kind of the elements, or [`None`] for *TAG_End*.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Option<Kind> {
+        self.kind
+    }
+
+    /// Returns the number of elements in the list.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list contains no elements.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the raw bytes of the payloads of the elements, without the
+    /// element kind and the length prefix.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the element at the given index, or [`None`] if out of bounds.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<View<'a>> {
+        if index >= self.len {
+            return None;
+        }
+        let kind = self.kind?;
+        match fixed_size(kind) {
+            Some(size) => {
+                let mut input = &self.bytes[index * size..];
+                Some(View::read_valid(kind, &mut input))
+            }
+            None => self.iter().nth(index),
+        }
+    }
+
+    /// Returns an iterator over the elements.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a> {
+        Iter {
+            kind: self.kind,
+            remaining: self.len,
+            input: self.bytes,
+        }
+    }
+
+    /// Decodes the view into an owned list.
+    ///
+    /// A list of compounds that contains any wrapper is unwrapped into a
+    /// mixed list, like [`binary::read`](crate::binary::read).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a string is not well-formed MUTF-8.
+    pub fn to_owned(&self) -> Result<List, NbtError> {
+        let mut list = match self.kind {
+            Some(kind) => List::with_kind(kind),
+            None => List::End,
+        };
+        for element in self {
+            // The element is always of the list kind.
+            let _ = list.push(element.to_owned()?);
+        }
+        Ok(match list {
+            List::Compound(vec) if vec.iter().any(is_wrapper) => {
+                List::Mixed(vec.into_iter().map(unwrap).collect())
+            }
+            list => list,
+        })
+    }
+}
+
+impl Debug for ListView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for ListView<'a> {
+    type Item = View<'a>;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &ListView<'a> {
+    type Item = View<'a>;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a [`ListView`].
+///
+/// This is created by [`ListView::iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<'a> {
+    kind: Option<Kind>,
+    remaining: usize,
+    input: &'a [u8],
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = View<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(View::read_valid(self.kind?, &mut self.input))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+/// Returns the size of the payload if the kind is a fixed-size number.
+pub(crate) const fn fixed_size(kind: Kind) -> Option<usize> {
+    match kind {
+        Kind::Byte => Some(1),
+        Kind::Short => Some(2),
+        Kind::Int | Kind::Float => Some(4),
+        Kind::Long | Kind::Double => Some(8),
+        _ => None,
+    }
+}
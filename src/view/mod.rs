@@ -0,0 +1,443 @@
+//! This module provides a zero-copy view of binary NBT, see [`View`].
+//!
+//! The view borrows the input rather than decoding it into a value tree, which
+//! avoids allocating when only a few tags are needed, e.g., scanning the
+//! chunks of a region file:
+//!
+//! ```
+//! use znbt::value::{Compound, List};
+//!
+//! let mut chunk = Compound::new();
+//! chunk.insert("DataVersion", 3953);
+//! chunk.insert("sections", List::Compound(vec![Compound::from_iter([("Y", -4i8)])]));
+//! let mut bytes = Vec::new();
+//! znbt::binary::write(&mut bytes, "", &chunk, znbt::binary::Java)?;
+//!
+//! let (_, root) = znbt::view::read(&mut &bytes[..])?;
+//! assert_eq!(root.get("DataVersion").and_then(|v| v.as_int()), Some(3953));
+//! let sections = root.get("sections").and_then(|v| v.as_list()).unwrap();
+//! for section in sections {
+//!     let y = section.as_compound().and_then(|s| s.get("Y"));
+//!     assert_eq!(y.and_then(|v| v.as_byte()), Some(-4));
+//! }
+//! # Ok::<(), znbt::error::NbtError>(())
+//! ```
+//!
+//! Reading a view validates the structure of the tags in a single pass, i.e.,
+//! the tag IDs, the length prefixes and the nesting depth, so that walking the
+//! view never fails afterwards. The payloads are decoded on access: numbers
+//! are read from the input, strings are [`StrView`]s of the raw MUTF-8 bytes,
+//! and int and long arrays are [`ArrayView`]s of the unaligned big-endian
+//! bytes.
+//!
+//! The view supports the [`Java`](crate::binary::Java) flavor only. A mixed
+//! list is viewed as it is on the wire, i.e., a list of compounds.
+
+pub mod array;
+pub mod compound;
+pub mod list;
+mod seek;
+
+use alloc::borrow::Cow;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::slice;
+
+use crate::binary::{take, take_array};
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::mutf8;
+use crate::value::Value;
+
+pub use self::array::ArrayView;
+pub use self::compound::CompoundView;
+pub use self::list::ListView;
+pub use self::seek::{find_path, skip};
+
+/// Reads a view of a named root compound from the input, returning the root
+/// name and the compound.
+///
+/// The input is advanced past the root tag, see [`binary::read`].
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed binary
+/// NBT, or the root tag is not a compound. The strings are not validated.
+///
+/// [`binary::read`]: crate::binary::read
+pub fn read<'a>(input: &mut &'a [u8]) -> Result<(StrView<'a>, CompoundView<'a>), NbtError> {
+    let kind = read_kind(input)?;
+    if kind != Some(Kind::Compound) {
+        return Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: kind,
+        });
+    }
+    let name = read_str(input)?;
+    let compound = CompoundView::read(input, 0)?;
+    Ok((name, compound))
+}
+
+/// Reads a view of a nameless root tag of any kind from the input.
+///
+/// The input is advanced past the root tag, see [`binary::read_nameless`].
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed binary
+/// NBT, or the root tag is *TAG_End*. The strings are not validated.
+///
+/// [`binary::read_nameless`]: crate::binary::read_nameless
+pub fn read_nameless<'a>(input: &mut &'a [u8]) -> Result<View<'a>, NbtError> {
+    match read_kind(input)? {
+        Some(kind) => read_payload(kind, input),
+        None => Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: None,
+        }),
+    }
+}
+
+/// Reads a view of the payload of the kind from the input, e.g., the range
+/// found by [`find_path`].
+///
+/// The input is advanced past the payload.
+///
+/// # Errors
+///
+/// This function returns an error if the payload is not well-formed. The
+/// strings are not validated.
+#[inline]
+pub fn read_payload<'a>(kind: Kind, input: &mut &'a [u8]) -> Result<View<'a>, NbtError> {
+    View::read(kind, input, 0)
+}
+
+/// A zero-copy view of an NBT value, borrowing the binary input.
+///
+/// There is exactly one variant for each [`Kind`], like [`Value`]. See the
+/// [module documentation](self) for how the payloads are decoded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum View<'a> {
+    /// See [`Kind::Byte`].
+    Byte(i8),
+    /// See [`Kind::Short`].
+    Short(i16),
+    /// See [`Kind::Int`].
+    Int(i32),
+    /// See [`Kind::Long`].
+    Long(i64),
+    /// See [`Kind::Float`].
+    Float(f32),
+    /// See [`Kind::Double`].
+    Double(f64),
+    /// See [`Kind::ByteArray`].
+    ByteArray(&'a [i8]),
+    /// See [`Kind::String`].
+    String(StrView<'a>),
+    /// See [`Kind::List`].
+    List(ListView<'a>),
+    /// See [`Kind::Compound`].
+    Compound(CompoundView<'a>),
+    /// See [`Kind::IntArray`].
+    IntArray(ArrayView<'a, i32>),
+    /// See [`Kind::LongArray`].
+    LongArray(ArrayView<'a, i64>),
+}
+
+impl<'a> View<'a> {
+    /// Reads the payload of the kind, validating the nested tags.
+    pub(crate) fn read(kind: Kind, input: &mut &'a [u8], depth: usize) -> Result<Self, NbtError> {
+        Ok(match kind {
+            Kind::Byte => View::Byte(i8::from_be_bytes(take_array(input)?)),
+            Kind::Short => View::Short(i16::from_be_bytes(take_array(input)?)),
+            Kind::Int => View::Int(i32::from_be_bytes(take_array(input)?)),
+            Kind::Long => View::Long(i64::from_be_bytes(take_array(input)?)),
+            Kind::Float => View::Float(f32::from_be_bytes(take_array(input)?)),
+            Kind::Double => View::Double(f64::from_be_bytes(take_array(input)?)),
+            Kind::ByteArray => {
+                let len = read_len(input)?;
+                let bytes = take(input, len)?;
+                // SAFETY: `i8` has the same layout as `u8`.
+                View::ByteArray(unsafe { slice::from_raw_parts(bytes.as_ptr().cast(), len) })
+            }
+            Kind::String => View::String(read_str(input)?),
+            Kind::List => View::List(ListView::read(input, depth)?),
+            Kind::Compound => View::Compound(CompoundView::read(input, depth)?),
+            Kind::IntArray => View::IntArray(ArrayView::read(input)?),
+            Kind::LongArray => View::LongArray(ArrayView::read(input)?),
+        })
+    }
+
+    /// Reads the payload of a tag that is already validated.
+    pub(crate) fn read_valid(kind: Kind, input: &mut &'a [u8]) -> Self {
+        match View::read(kind, input, 0) {
+            Ok(view) => view,
+            Err(_) => unreachable!("the tag is validated when the view is read"),
+        }
+    }
+
+    /// Returns the kind of the value.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> Kind {
+        match self {
+            View::Byte(_) => Kind::Byte,
+            View::Short(_) => Kind::Short,
+            View::Int(_) => Kind::Int,
+            View::Long(_) => Kind::Long,
+            View::Float(_) => Kind::Float,
+            View::Double(_) => Kind::Double,
+            View::ByteArray(_) => Kind::ByteArray,
+            View::String(_) => Kind::String,
+            View::List(_) => Kind::List,
+            View::Compound(_) => Kind::Compound,
+            View::IntArray(_) => Kind::IntArray,
+            View::LongArray(_) => Kind::LongArray,
+        }
+    }
+
+    /// Returns the byte if the value is a [`Kind::Byte`].
+    #[inline]
+    #[must_use]
+    pub const fn as_byte(&self) -> Option<i8> {
+        match *self {
+            View::Byte(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean if the value is a [`Kind::Byte`], where any
+    /// non-zero byte is regarded as `true`.
+    #[inline]
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match *self {
+            View::Byte(value) => Some(value != 0),
+            _ => None,
+        }
+    }
+
+    /// Returns the short if the value is a [`Kind::Short`].
+    #[inline]
+    #[must_use]
+    pub const fn as_short(&self) -> Option<i16> {
+        match *self {
+            View::Short(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the int if the value is a [`Kind::Int`].
+    #[inline]
+    #[must_use]
+    pub const fn as_int(&self) -> Option<i32> {
+        match *self {
+            View::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the long if the value is a [`Kind::Long`].
+    #[inline]
+    #[must_use]
+    pub const fn as_long(&self) -> Option<i64> {
+        match *self {
+            View::Long(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the float if the value is a [`Kind::Float`].
+    #[inline]
+    #[must_use]
+    pub const fn as_float(&self) -> Option<f32> {
+        match *self {
+            View::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the double if the value is a [`Kind::Double`].
+    #[inline]
+    #[must_use]
+    pub const fn as_double(&self) -> Option<f64> {
+        match *self {
+            View::Double(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes if the value is a [`Kind::ByteArray`].
+    #[inline]
+    #[must_use]
+    pub const fn as_byte_array(&self) -> Option<&'a [i8]> {
+        match *self {
+            View::ByteArray(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is a [`Kind::String`].
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> Option<StrView<'a>> {
+        match *self {
+            View::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the list if the value is a [`Kind::List`].
+    #[inline]
+    #[must_use]
+    pub const fn as_list(&self) -> Option<ListView<'a>> {
+        match *self {
+            View::List(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the compound if the value is a [`Kind::Compound`].
+    #[inline]
+    #[must_use]
+    pub const fn as_compound(&self) -> Option<CompoundView<'a>> {
+        match *self {
+            View::Compound(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the ints if the value is a [`Kind::IntArray`].
+    #[inline]
+    #[must_use]
+    pub const fn as_int_array(&self) -> Option<ArrayView<'a, i32>> {
+        match *self {
+            View::IntArray(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the longs if the value is a [`Kind::LongArray`].
+    #[inline]
+    #[must_use]
+    pub const fn as_long_array(&self) -> Option<ArrayView<'a, i64>> {
+        match *self {
+            View::LongArray(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Decodes the view into an owned value.
+    ///
+    /// A list of compounds that contains any wrapper is unwrapped into a
+    /// mixed list, like [`binary::read`](crate::binary::read).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a string is not well-formed MUTF-8.
+    pub fn to_owned(&self) -> Result<Value, NbtError> {
+        Ok(match *self {
+            View::Byte(value) => Value::Byte(value),
+            View::Short(value) => Value::Short(value),
+            View::Int(value) => Value::Int(value),
+            View::Long(value) => Value::Long(value),
+            View::Float(value) => Value::Float(value),
+            View::Double(value) => Value::Double(value),
+            View::ByteArray(value) => Value::from(value),
+            View::String(value) => Value::from(&*value.to_str()?),
+            View::List(value) => Value::from(value.to_owned()?),
+            View::Compound(value) => Value::from(value.to_owned()?),
+            View::IntArray(value) => Value::from(value.to_vec()),
+            View::LongArray(value) => Value::from(value.to_vec()),
+        })
+    }
+}
+
+/// A zero-copy view of a string, borrowing the raw MUTF-8 bytes.
+///
+/// The bytes are validated on decoding, see [`StrView::to_str`]. Comparing
+/// with a `str` encodes it instead, which borrows the string unless it
+/// contains null or supplementary characters.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StrView<'a>(&'a [u8]);
+
+impl<'a> StrView<'a> {
+    /// Returns the raw MUTF-8 bytes.
+    #[inline]
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// Decodes the string, borrowing the bytes if they are valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the bytes are not well-formed.
+    pub fn to_str(&self) -> Result<Cow<'a, str>, NbtError> {
+        mutf8::decode(self.0).map_err(|_| NbtError::InvalidString)
+    }
+
+    /// Decodes the string, replacing any ill-formed sequence with `U+FFFD`.
+    #[must_use]
+    pub fn to_str_lossy(&self) -> Cow<'a, str> {
+        mutf8::decode_lossy(self.0)
+    }
+}
+
+impl PartialEq<str> for StrView<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        *self.0 == *mutf8::encode(other)
+    }
+}
+
+impl PartialEq<&str> for StrView<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Debug for StrView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.to_str_lossy(), f)
+    }
+}
+
+impl Display for StrView<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.to_str_lossy(), f)
+    }
+}
+
+/// Reads a tag ID, where [`None`] means *TAG_End*.
+fn read_kind(input: &mut &[u8]) -> Result<Option<Kind>, NbtError> {
+    match take_array::<1>(input)? {
+        [0] => Ok(None),
+        [id] => Ok(Some(Kind::new(id)?)),
+    }
+}
+
+/// Reads the length prefix of an array or list.
+fn read_len(input: &mut &[u8]) -> Result<usize, NbtError> {
+    let len = i32::from_be_bytes(take_array(input)?);
+    usize::try_from(len).map_err(|_| NbtError::NegativeLength(len))
+}
+
+fn read_str<'a>(input: &mut &'a [u8]) -> Result<StrView<'a>, NbtError> {
+    let len = u16::from_be_bytes(take_array(input)?);
+    Ok(StrView(take(input, usize::from(len))?))
+}
+
+/// Enters a compound or list, returning the nesting depth inside it.
+fn enter(depth: usize) -> Result<usize, NbtError> {
+    if depth >= NbtError::MAX_DEPTH {
+        return Err(NbtError::DepthLimit);
+    }
+    Ok(depth + 1)
+}
+
+/// Returns the bytes between the start and the rest of the input.
+fn consumed<'a>(start: &'a [u8], rest: &'a [u8]) -> &'a [u8] {
+    &start[..start.len() - rest.len()]
+}
@@ -0,0 +1,138 @@
+use core::ops::Range;
+
+use crate::binary::take;
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::mutf8;
+use crate::view::list::fixed_size;
+use crate::view::{enter, read_kind, read_len, read_str};
+
+/// Skips the payload of the kind without decoding it, advancing the input past
+/// the payload.
+///
+/// The strings and arrays are skipped by their length prefixes, and the
+/// compounds and lists are walked recursively, where a list of numbers is
+/// skipped at once.
+///
+/// # Errors
+///
+/// This function returns an error if the payload is not well-formed, e.g., the
+/// input is too short.
+#[inline]
+pub fn skip(kind: Kind, input: &mut &[u8]) -> Result<(), NbtError> {
+    skip_payload(kind, input, 0)
+}
+
+/// Skips the payload of the kind inside the given nesting depth.
+pub(crate) fn skip_payload(kind: Kind, input: &mut &[u8], depth: usize) -> Result<(), NbtError> {
+    match kind {
+        Kind::Byte => skip_bytes(input, 1, 1),
+        Kind::Short => skip_bytes(input, 1, 2),
+        Kind::Int | Kind::Float => skip_bytes(input, 1, 4),
+        Kind::Long | Kind::Double => skip_bytes(input, 1, 8),
+        Kind::ByteArray => skip_array(input, 1),
+        Kind::String => read_str(input).map(|_| ()),
+        Kind::List => {
+            let depth = enter(depth)?;
+            let kind = read_kind(input)?;
+            let len = read_len(input)?;
+            match kind {
+                None if len == 0 => Ok(()),
+                None => Err(NbtError::UnexpectedKind {
+                    expected: Kind::List,
+                    found: None,
+                }),
+                Some(kind) => match fixed_size(kind) {
+                    Some(size) => skip_bytes(input, len, size),
+                    None => (0..len).try_for_each(|_| skip_payload(kind, input, depth)),
+                },
+            }
+        }
+        Kind::Compound => {
+            let depth = enter(depth)?;
+            while let Some(kind) = read_kind(input)? {
+                read_str(input)?;
+                skip_payload(kind, input, depth)?;
+            }
+            Ok(())
+        }
+        Kind::IntArray => skip_array(input, 4),
+        Kind::LongArray => skip_array(input, 8),
+    }
+}
+
+/// Skips the length prefix and the elements of an array.
+fn skip_array(input: &mut &[u8], size: usize) -> Result<(), NbtError> {
+    let len = read_len(input)?;
+    skip_bytes(input, len, size)
+}
+
+/// Skips `len` elements of `size` bytes.
+fn skip_bytes(input: &mut &[u8], len: usize, size: usize) -> Result<(), NbtError> {
+    let total = len.checked_mul(size).ok_or(NbtError::UnexpectedEof)?;
+    take(input, total).map(|_| ())
+}
+
+/// Finds the tag at the path of keys in a named root compound, returning its
+/// kind and the byte range of its payload in the input.
+///
+/// Only the entries before the target are skipped, so the rest of the input
+/// is neither decoded nor validated. The payload can be viewed by
+/// [`read_payload`](crate::view::read_payload):
+///
+/// ```
+/// use znbt::kind::Kind;
+/// use znbt::value::Compound;
+/// use znbt::view::{self, View};
+///
+/// let level = Compound::from_iter([("xPos", 3), ("zPos", -2)]);
+/// let mut bytes = Vec::new();
+/// let root = Compound::from_iter([("Level", level)]);
+/// znbt::binary::write(&mut bytes, "", &root, znbt::binary::Java)?;
+///
+/// let (kind, range) = view::find_path(&bytes, &["Level", "zPos"])?.unwrap();
+/// assert_eq!(kind, Kind::Int);
+/// assert_eq!(view::read_payload(kind, &mut &bytes[range])?, View::Int(-2));
+/// # Ok::<(), znbt::error::NbtError>(())
+/// ```
+///
+/// An empty path finds the root compound itself.
+///
+/// # Errors
+///
+/// This function returns an error if the input is not a well-formed binary
+/// NBT before the target, or the root tag is not a compound. It returns
+/// `Ok(None)` if any key is not found, or a tag along the path is not a
+/// compound.
+pub fn find_path(bytes: &[u8], path: &[&str]) -> Result<Option<(Kind, Range<usize>)>, NbtError> {
+    let mut input = bytes;
+    let kind = read_kind(&mut input)?;
+    if kind != Some(Kind::Compound) {
+        return Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: kind,
+        });
+    }
+    read_str(&mut input)?;
+    let mut kind = Kind::Compound;
+    for key in path {
+        if kind != Kind::Compound {
+            return Ok(None);
+        }
+        let key = mutf8::encode(key);
+        loop {
+            let Some(found) = read_kind(&mut input)? else {
+                return Ok(None);
+            };
+            if *read_str(&mut input)?.as_bytes() == *key {
+                kind = found;
+                break;
+            }
+            skip(found, &mut input)?;
+        }
+    }
+    let start = bytes.len() - input.len();
+    skip(kind, &mut input)?;
+    let end = bytes.len() - input.len();
+    Ok(Some((kind, start..end)))
+}
@@ -0,0 +1,482 @@
+//! This module provides the visitors of value trees, which walk the tags in
+//! depth-first order with a callback for each [`Kind`], so that a traversal
+//! does not need a recursive match by hand.
+//!
+//! A [`Visitor`] reads the tags, and can walk both the owned tree and the
+//! borrowed [`view`](crate::view), since both implement [`Walk`]. A
+//! [`VisitorMut`] modifies the tags of the owned tree in place, see
+//! [`WalkMut`]. Every callback receives the [`Path`] from the root to the tag.
+//!
+//! ```
+//! use znbt::visit::{Path, Visitor, Walk};
+//!
+//! /// Collects the paths of all strings.
+//! struct Strings(Vec<String>);
+//!
+//! impl Visitor for Strings {
+//!     fn visit_string(&mut self, path: &Path, value: &str) {
+//!         self.0.push(format!("{path} = {value}"));
+//!     }
+//! }
+//!
+//! let value: znbt::value::Value = r#"{id: "minecraft:chest", Items: [{id: "minecraft:stone"}]}"#.parse()?;
+//! let mut strings = Strings(Vec::new());
+//! value.walk(&mut strings)?;
+//! assert_eq!(strings.0, ["id = minecraft:chest", "Items[0].id = minecraft:stone"]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+mod path;
+
+use alloc::vec::Vec;
+
+use crate::error::NbtError;
+use crate::kind::Kind;
+use crate::value::{ByteArray, Compound, IntArray, List, LongArray, ThinStr, Value, ValueRef};
+use crate::view::{CompoundView, ListView, View};
+
+pub(crate) use self::path::write_key;
+pub use self::path::{Path, Segment};
+
+/// A visitor of the tags of a value tree, see [`Walk`].
+///
+/// Every callback does nothing by default. A list or compound is visited by
+/// the enter callback, which returns whether to visit its elements or
+/// entries, followed by them and the leave callback if so.
+///
+/// A [`List::Mixed`] is visited with the element kind [`Kind::Compound`], like
+/// [`List::kind`], and its elements unwrapped, in both the owned tree and the
+/// view.
+#[allow(unused_variables)]
+pub trait Visitor {
+    /// Visits a [`Kind::Byte`].
+    fn visit_byte(&mut self, path: &Path, value: i8) {}
+
+    /// Visits a [`Kind::Short`].
+    fn visit_short(&mut self, path: &Path, value: i16) {}
+
+    /// Visits a [`Kind::Int`].
+    fn visit_int(&mut self, path: &Path, value: i32) {}
+
+    /// Visits a [`Kind::Long`].
+    fn visit_long(&mut self, path: &Path, value: i64) {}
+
+    /// Visits a [`Kind::Float`].
+    fn visit_float(&mut self, path: &Path, value: f32) {}
+
+    /// Visits a [`Kind::Double`].
+    fn visit_double(&mut self, path: &Path, value: f64) {}
+
+    /// Visits a [`Kind::ByteArray`].
+    fn visit_byte_array(&mut self, path: &Path, value: &[i8]) {}
+
+    /// Visits a [`Kind::String`].
+    fn visit_string(&mut self, path: &Path, value: &str) {}
+
+    /// Visits a [`Kind::IntArray`].
+    fn visit_int_array(&mut self, path: &Path, value: &[i32]) {}
+
+    /// Visits a [`Kind::LongArray`].
+    fn visit_long_array(&mut self, path: &Path, value: &[i64]) {}
+
+    /// Enters a [`Kind::List`] of the element kind and length, where [`None`]
+    /// means *TAG_End*, returning whether to visit the elements.
+    fn enter_list(&mut self, path: &Path, kind: Option<Kind>, len: usize) -> bool {
+        true
+    }
+
+    /// Leaves a [`Kind::List`] after its elements.
+    fn leave_list(&mut self, path: &Path) {}
+
+    /// Enters a [`Kind::Compound`] of the number of entries, returning
+    /// whether to visit the entries.
+    fn enter_compound(&mut self, path: &Path, len: usize) -> bool {
+        true
+    }
+
+    /// Leaves a [`Kind::Compound`] after its entries.
+    fn leave_compound(&mut self, path: &Path) {}
+}
+
+/// A visitor that modifies the tags of an owned value tree in place, see
+/// [`WalkMut`].
+///
+/// Like [`Visitor`], every callback does nothing by default. The enter
+/// callback of a list or compound may modify it before its elements or
+/// entries are visited, e.g., renaming or removing the keys, and the leave
+/// callback after.
+#[allow(unused_variables)]
+pub trait VisitorMut {
+    /// Visits a [`Kind::Byte`].
+    fn visit_byte(&mut self, path: &Path, value: &mut i8) {}
+
+    /// Visits a [`Kind::Short`].
+    fn visit_short(&mut self, path: &Path, value: &mut i16) {}
+
+    /// Visits a [`Kind::Int`].
+    fn visit_int(&mut self, path: &Path, value: &mut i32) {}
+
+    /// Visits a [`Kind::Long`].
+    fn visit_long(&mut self, path: &Path, value: &mut i64) {}
+
+    /// Visits a [`Kind::Float`].
+    fn visit_float(&mut self, path: &Path, value: &mut f32) {}
+
+    /// Visits a [`Kind::Double`].
+    fn visit_double(&mut self, path: &Path, value: &mut f64) {}
+
+    /// Visits a [`Kind::ByteArray`].
+    fn visit_byte_array(&mut self, path: &Path, value: &mut ByteArray) {}
+
+    /// Visits a [`Kind::String`].
+    fn visit_string(&mut self, path: &Path, value: &mut ThinStr) {}
+
+    /// Visits a [`Kind::IntArray`].
+    fn visit_int_array(&mut self, path: &Path, value: &mut IntArray) {}
+
+    /// Visits a [`Kind::LongArray`].
+    fn visit_long_array(&mut self, path: &Path, value: &mut LongArray) {}
+
+    /// Enters a [`Kind::List`], returning whether to visit the elements.
+    fn enter_list(&mut self, path: &Path, list: &mut List) -> bool {
+        true
+    }
+
+    /// Leaves a [`Kind::List`] after its elements.
+    fn leave_list(&mut self, path: &Path, list: &mut List) {}
+
+    /// Enters a [`Kind::Compound`], returning whether to visit the entries.
+    fn enter_compound(&mut self, path: &Path, compound: &mut Compound) -> bool {
+        true
+    }
+
+    /// Leaves a [`Kind::Compound`] after its entries.
+    fn leave_compound(&mut self, path: &Path, compound: &mut Compound) {}
+}
+
+/// A value tree that can be walked by a [`Visitor`].
+///
+/// This is implemented by the owned tree, i.e., [`Value`], [`ValueRef`],
+/// [`List`] and [`Compound`], and by the [`view`](crate::view), i.e.,
+/// [`View`], [`ListView`] and [`CompoundView`]. The path of the walked value
+/// itself is the root.
+pub trait Walk {
+    /// Walks the tags in depth-first order, calling the visitor on each.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a key or string of a view is not
+    /// well-formed MUTF-8. Walking the owned tree never fails.
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError>;
+}
+
+/// An owned value tree that can be walked by a [`VisitorMut`].
+///
+/// This is implemented by [`Value`], [`List`] and [`Compound`]. The path of
+/// the walked value itself is the root.
+pub trait WalkMut {
+    /// Walks the tags in depth-first order, calling the visitor on each.
+    fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V);
+}
+
+impl Walk for Value {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).value(self.as_borrowed());
+        Ok(())
+    }
+}
+
+impl Walk for ValueRef<'_> {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).value(*self);
+        Ok(())
+    }
+}
+
+impl Walk for List {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).list(self);
+        Ok(())
+    }
+}
+
+impl Walk for Compound {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).compound(self);
+        Ok(())
+    }
+}
+
+impl Walk for View<'_> {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).view(*self)
+    }
+}
+
+impl Walk for ListView<'_> {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).list_view(*self)
+    }
+}
+
+impl Walk for CompoundView<'_> {
+    #[inline]
+    fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V) -> Result<(), NbtError> {
+        Walker::new(visitor).compound_view(*self)
+    }
+}
+
+impl WalkMut for Value {
+    #[inline]
+    fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        WalkerMut::new(visitor).value(self);
+    }
+}
+
+impl WalkMut for List {
+    #[inline]
+    fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        WalkerMut::new(visitor).list(self);
+    }
+}
+
+impl WalkMut for Compound {
+    #[inline]
+    fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        WalkerMut::new(visitor).compound(self);
+    }
+}
+
+/// The state of a walk by a [`Visitor`].
+struct Walker<'v, V: ?Sized> {
+    visitor: &'v mut V,
+    path: Path,
+    /// The buffers of the decoded arrays of a view.
+    ints: Vec<i32>,
+    longs: Vec<i64>,
+}
+
+impl<'v, V: Visitor + ?Sized> Walker<'v, V> {
+    fn new(visitor: &'v mut V) -> Self {
+        Walker {
+            visitor,
+            path: Path::new(),
+            ints: Vec::new(),
+            longs: Vec::new(),
+        }
+    }
+
+    fn value(&mut self, value: ValueRef<'_>) {
+        let path = &self.path;
+        match value {
+            ValueRef::Byte(value) => self.visitor.visit_byte(path, value),
+            ValueRef::Short(value) => self.visitor.visit_short(path, value),
+            ValueRef::Int(value) => self.visitor.visit_int(path, value),
+            ValueRef::Long(value) => self.visitor.visit_long(path, value),
+            ValueRef::Float(value) => self.visitor.visit_float(path, value),
+            ValueRef::Double(value) => self.visitor.visit_double(path, value),
+            ValueRef::ByteArray(value) => self.visitor.visit_byte_array(path, value),
+            ValueRef::String(value) => self.visitor.visit_string(path, value),
+            ValueRef::List(list) => self.list(list),
+            ValueRef::Compound(compound) => self.compound(compound),
+            ValueRef::IntArray(value) => self.visitor.visit_int_array(path, value),
+            ValueRef::LongArray(value) => self.visitor.visit_long_array(path, value),
+        }
+    }
+
+    fn list(&mut self, list: &List) {
+        if !self.visitor.enter_list(&self.path, list.kind(), list.len()) {
+            return;
+        }
+        for (index, element) in list.iter().enumerate() {
+            self.path.push_index(index);
+            self.value(element);
+            self.path.pop();
+        }
+        self.visitor.leave_list(&self.path);
+    }
+
+    fn compound(&mut self, compound: &Compound) {
+        if !self.visitor.enter_compound(&self.path, compound.len()) {
+            return;
+        }
+        for (key, value) in compound {
+            self.path.push_key(key);
+            self.value(value.as_borrowed());
+            self.path.pop();
+        }
+        self.visitor.leave_compound(&self.path);
+    }
+
+    fn view(&mut self, view: View<'_>) -> Result<(), NbtError> {
+        let path = &self.path;
+        match view {
+            View::Byte(value) => self.visitor.visit_byte(path, value),
+            View::Short(value) => self.visitor.visit_short(path, value),
+            View::Int(value) => self.visitor.visit_int(path, value),
+            View::Long(value) => self.visitor.visit_long(path, value),
+            View::Float(value) => self.visitor.visit_float(path, value),
+            View::Double(value) => self.visitor.visit_double(path, value),
+            View::ByteArray(value) => self.visitor.visit_byte_array(path, value),
+            View::String(value) => self.visitor.visit_string(path, &value.to_str()?),
+            View::List(list) => return self.list_view(list),
+            View::Compound(compound) => return self.compound_view(compound),
+            View::IntArray(array) => {
+                self.ints.clear();
+                self.ints.extend(array.iter());
+                self.visitor.visit_int_array(path, &self.ints);
+            }
+            View::LongArray(array) => {
+                self.longs.clear();
+                self.longs.extend(array.iter());
+                self.visitor.visit_long_array(path, &self.longs);
+            }
+        }
+        Ok(())
+    }
+
+    fn list_view(&mut self, list: ListView<'_>) -> Result<(), NbtError> {
+        if !self.visitor.enter_list(&self.path, list.kind(), list.len()) {
+            return Ok(());
+        }
+        // The elements of a mixed list are unwrapped like `ListView::to_owned`.
+        let mixed = list.iter().any(|element| wrapped(element).is_some());
+        for (index, element) in list.iter().enumerate() {
+            self.path.push_index(index);
+            let element = match wrapped(element) {
+                Some(inner) if mixed => inner,
+                _ => element,
+            };
+            self.view(element)?;
+            self.path.pop();
+        }
+        self.visitor.leave_list(&self.path);
+        Ok(())
+    }
+
+    fn compound_view(&mut self, compound: CompoundView<'_>) -> Result<(), NbtError> {
+        if !self.visitor.enter_compound(&self.path, compound.len()) {
+            return Ok(());
+        }
+        for (key, value) in compound {
+            self.path.push_key(&key.to_str()?);
+            self.view(value)?;
+            self.path.pop();
+        }
+        self.visitor.leave_compound(&self.path);
+        Ok(())
+    }
+}
+
+/// Returns the value of a wrapper of a mixed list element, i.e., a compound
+/// of a single entry with an empty key.
+fn wrapped(view: View<'_>) -> Option<View<'_>> {
+    let mut entries = view.as_compound()?.iter();
+    match (entries.next(), entries.next()) {
+        (Some((key, value)), None) if key.as_bytes().is_empty() => Some(value),
+        _ => None,
+    }
+}
+
+/// The state of a walk by a [`VisitorMut`].
+struct WalkerMut<'v, V: ?Sized> {
+    visitor: &'v mut V,
+    path: Path,
+}
+
+impl<'v, V: VisitorMut + ?Sized> WalkerMut<'v, V> {
+    fn new(visitor: &'v mut V) -> Self {
+        WalkerMut {
+            visitor,
+            path: Path::new(),
+        }
+    }
+
+    fn value(&mut self, value: &mut Value) {
+        let path = &self.path;
+        match value {
+            Value::Byte(value) => self.visitor.visit_byte(path, value),
+            Value::Short(value) => self.visitor.visit_short(path, value),
+            Value::Int(value) => self.visitor.visit_int(path, value),
+            Value::Long(value) => self.visitor.visit_long(path, value),
+            Value::Float(value) => self.visitor.visit_float(path, value),
+            Value::Double(value) => self.visitor.visit_double(path, value),
+            Value::ByteArray(value) => self.visitor.visit_byte_array(path, value),
+            Value::String(value) => self.visitor.visit_string(path, value),
+            Value::List(list) => self.list(list),
+            Value::Compound(compound) => self.compound(compound),
+            Value::IntArray(value) => self.visitor.visit_int_array(path, value),
+            Value::LongArray(value) => self.visitor.visit_long_array(path, value),
+        }
+    }
+
+    fn list(&mut self, list: &mut List) {
+        if !self.visitor.enter_list(&self.path, list) {
+            return;
+        }
+        match list {
+            List::End => {}
+            List::Byte(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_byte(&this.path, value);
+            }),
+            List::Short(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_short(&this.path, value);
+            }),
+            List::Int(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_int(&this.path, value);
+            }),
+            List::Long(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_long(&this.path, value);
+            }),
+            List::Float(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_float(&this.path, value);
+            }),
+            List::Double(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_double(&this.path, value);
+            }),
+            List::ByteArray(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_byte_array(&this.path, value);
+            }),
+            List::String(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_string(&this.path, value);
+            }),
+            List::List(vec) => self.each(vec, Self::list),
+            List::Compound(vec) => self.each(vec, Self::compound),
+            List::IntArray(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_int_array(&this.path, value);
+            }),
+            List::LongArray(vec) => self.each(vec, |this, value| {
+                this.visitor.visit_long_array(&this.path, value);
+            }),
+            List::Mixed(vec) => self.each(vec, Self::value),
+        }
+        self.visitor.leave_list(&self.path, list);
+    }
+
+    /// Visits each element of a typed vector with its index.
+    fn each<T>(&mut self, elements: &mut [T], mut visit: impl FnMut(&mut Self, &mut T)) {
+        for (index, element) in elements.iter_mut().enumerate() {
+            self.path.push_index(index);
+            visit(self, element);
+            self.path.pop();
+        }
+    }
+
+    fn compound(&mut self, compound: &mut Compound) {
+        if !self.visitor.enter_compound(&self.path, compound) {
+            return;
+        }
+        for (key, value) in compound.iter_mut() {
+            self.path.push_key(key);
+            self.value(value);
+            self.path.pop();
+        }
+        self.visitor.leave_compound(&self.path, compound);
+    }
+}
@@ -0,0 +1,147 @@
+//! The [`Path`] type and its segments.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::snbt::{is_unquoted, quote};
+
+/// A segment of a [`Path`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Segment<'a> {
+    /// The key of an entry of a compound.
+    Key(&'a str),
+    /// The index of an element of a list.
+    Index(usize),
+}
+
+/// A stored segment, where the key is a range of the key buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Step {
+    Key { start: usize, end: usize },
+    Index(usize),
+}
+
+/// The path from the root to a value, i.e., a sequence of compound keys and
+/// list indices.
+///
+/// The keys are stored in a single buffer, so that pushing and popping the
+/// segments does not allocate once the buffer is large enough. This is how a
+/// [`Visitor`](crate::visit::Visitor) tracks where it is in the tree.
+///
+/// The [`Display`] of a path is like the NBT path of commands, e.g.,
+/// `Inventory[0].tag."display name"`, where a key is quoted if it is empty
+/// or contains any character other than `0-9`, `A-Z`, `a-z`, `_`, `-` and
+/// `+`. The root is the empty path, which is displayed as an empty string.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Path {
+    keys: String,
+    steps: Vec<Step>,
+}
+
+impl Path {
+    /// Creates an empty path, i.e., the root.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Path {
+            keys: String::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Returns the number of segments in the path.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if the path has no segments, i.e., is the root.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Returns the segment at the given index, or [`None`] if out of bounds.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Segment<'_>> {
+        self.steps.get(index).map(|&step| self.segment(step))
+    }
+
+    /// Returns the last segment, or [`None`] if the path is the root.
+    #[inline]
+    #[must_use]
+    pub fn last(&self) -> Option<Segment<'_>> {
+        self.steps.last().map(|&step| self.segment(step))
+    }
+
+    /// Returns an iterator over the segments from the root.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Segment<'_>> + ExactSizeIterator {
+        self.steps.iter().map(|&step| self.segment(step))
+    }
+
+    /// Appends the key of an entry of a compound.
+    pub fn push_key(&mut self, key: &str) {
+        let start = self.keys.len();
+        self.keys.push_str(key);
+        self.steps.push(Step::Key {
+            start,
+            end: self.keys.len(),
+        });
+    }
+
+    /// Appends the index of an element of a list.
+    pub fn push_index(&mut self, index: usize) {
+        self.steps.push(Step::Index(index));
+    }
+
+    /// Removes the last segment, returning `false` if the path is the root.
+    pub fn pop(&mut self) -> bool {
+        match self.steps.pop() {
+            Some(Step::Key { start, .. }) => {
+                self.keys.truncate(start);
+                true
+            }
+            Some(Step::Index(_)) => true,
+            None => false,
+        }
+    }
+
+    fn segment(&self, step: Step) -> Segment<'_> {
+        match step {
+            Step::Key { start, end } => Segment::Key(&self.keys[start..end]),
+            Step::Index(index) => Segment::Index(index),
+        }
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.iter().enumerate() {
+            match segment {
+                Segment::Key(key) => {
+                    if i > 0 {
+                        f.write_char('.')?;
+                    }
+                    write_key(f, key)?;
+                }
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the key of a path, which is quoted unless it is non-empty and only
+/// contains `0-9`, `A-Z`, `a-z`, `_`, `-` and `+`.
+pub(crate) fn write_key(f: &mut Formatter<'_>, key: &str) -> fmt::Result {
+    if !key.is_empty() && key.bytes().all(|byte| byte != b'.' && is_unquoted(byte)) {
+        f.write_str(key)
+    } else {
+        quote(f, key)
+    }
+}
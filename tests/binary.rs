@@ -0,0 +1,323 @@
+use znbt::binary::{self, Bedrock, BedrockNetwork, Java, Lossy};
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::value::{Compound, List, Value, ValueRef};
+
+/// The `hello_world.nbt` from the minecraft wiki.
+const HELLO_WORLD: &[u8] = b"\x0a\x00\x0bhello world\x08\x00\x04name\x00\x09Bananrama\x00";
+
+/// A compound containing every kind of tag.
+#[rustfmt::skip]
+const ALL_KINDS: &[u8] = &[
+    0x0a, 0x00, 0x04, b'r', b'o', b'o', b't',
+    0x01, 0x00, 0x01, b'b', 0xff,
+    0x02, 0x00, 0x01, b's', 0x01, 0x02,
+    0x03, 0x00, 0x01, b'i', 0x01, 0x02, 0x03, 0x04,
+    0x04, 0x00, 0x01, b'l', 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    0x05, 0x00, 0x01, b'f', 0x3f, 0x80, 0x00, 0x00,
+    0x06, 0x00, 0x01, b'd', 0xbf, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x07, 0x00, 0x02, b'b', b'a', 0x00, 0x00, 0x00, 0x02, 0x01, 0xfe,
+    0x08, 0x00, 0x03, b's', b't', b'r', 0x00, 0x02, b'h', b'i',
+    0x09, 0x00, 0x04, b'l', b'i', b's', b't', 0x03, 0x00, 0x00, 0x00, 0x02,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02,
+    0x09, 0x00, 0x05, b'e', b'm', b'p', b't', b'y', 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x09, 0x00, 0x05, b'n', b'e', b's', b't', b'd', 0x0a, 0x00, 0x00, 0x00, 0x01,
+        0x08, 0x00, 0x02, b'i', b'd', 0x00, 0x01, b'x',
+        0x00,
+    0x0a, 0x00, 0x03, b'c', b'p', b'd',
+        0x00,
+    0x0b, 0x00, 0x02, b'i', b'a', 0x00, 0x00, 0x00, 0x01, 0xff, 0xff, 0xff, 0xfe,
+    0x0c, 0x00, 0x02, b'l', b'a', 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2a,
+    0x00,
+];
+
+fn all_kinds() -> Compound {
+    let mut compound = Compound::new();
+    compound.insert("b", -1i8);
+    compound.insert("s", 0x0102i16);
+    compound.insert("i", 0x01020304);
+    compound.insert("l", 0x0102030405060708i64);
+    compound.insert("f", 1.0f32);
+    compound.insert("d", -1.0f64);
+    compound.insert("ba", vec![1i8, -2]);
+    compound.insert("str", "hi");
+    compound.insert("list", List::Int(vec![1, 2]));
+    compound.insert("empty", List::End);
+    let nested = Compound::from_iter([("id", "x")]);
+    compound.insert("nestd", List::Compound(vec![nested]));
+    compound.insert("cpd", Compound::new());
+    compound.insert("ia", vec![-2i32]);
+    compound.insert("la", vec![42i64]);
+    compound
+}
+
+fn encode(name: &str, compound: &Compound) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, name, compound, Java).unwrap();
+    bytes
+}
+
+#[test]
+fn read_hello_world() {
+    let mut input = HELLO_WORLD;
+    let (name, compound) = binary::read(&mut input, Java).unwrap();
+    assert_eq!(name, "hello world");
+    assert_eq!(compound.get("name"), Some(&Value::from("Bananrama")));
+    assert!(input.is_empty());
+}
+
+#[test]
+fn read_all_kinds() {
+    let (name, compound) = binary::read(&mut &ALL_KINDS[..], Java).unwrap();
+    assert_eq!(name, "root");
+    assert_eq!(compound, all_kinds());
+}
+
+#[test]
+fn write_all_kinds() {
+    assert_eq!(encode("root", &all_kinds()), ALL_KINDS);
+}
+
+#[test]
+fn round_trip() {
+    let (name, compound) = binary::read(&mut &HELLO_WORLD[..], Java).unwrap();
+    assert_eq!(encode(&name, &compound), HELLO_WORLD);
+}
+
+#[test]
+fn read_concatenated() {
+    let input = [HELLO_WORLD, ALL_KINDS].concat();
+    let mut input = &input[..];
+    assert_eq!(binary::read(&mut input, Java).unwrap().0, "hello world");
+    assert_eq!(binary::read(&mut input, Java).unwrap().0, "root");
+    assert!(input.is_empty());
+}
+
+#[test]
+fn reject_malformed() {
+    let error = binary::read(&mut &b"\x0d\x00\x00"[..], Java).unwrap_err();
+    assert!(matches!(error, NbtError::Kind(_)));
+
+    let error = binary::read(&mut &b"\x08\x00\x00\x00\x00"[..], Java).unwrap_err();
+    assert!(matches!(
+        error,
+        NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: Some(Kind::String)
+        }
+    ));
+
+    let error = binary::read(&mut &HELLO_WORLD[..HELLO_WORLD.len() - 1], Java).unwrap_err();
+    assert!(matches!(error, NbtError::UnexpectedEof));
+
+    let input = b"\x0a\x00\x00\x07\x00\x00\xff\xff\xff\xff\x00";
+    let error = binary::read(&mut &input[..], Java).unwrap_err();
+    assert!(matches!(error, NbtError::NegativeLength(-1)));
+}
+
+#[test]
+fn read_big_endian_fixtures() {
+    let input = b"\x0a\x00\x01r\x03\x00\x01i\xff\xff\xff\xfe\x05\x00\x01f\xc0\x00\x00\x00\x00";
+    let (name, compound) = binary::read(&mut &input[..], Java).unwrap();
+    assert_eq!(name, "r");
+    assert_eq!(compound.get("i"), Some(&Value::Int(-2)));
+    assert_eq!(compound.get("f"), Some(&Value::Float(-2.0)));
+
+    let input = b"\x0a\x00\x00\x09\x00\x01l\x02\x00\x00\x00\x02\x01\x00\xff\xff\x00";
+    let (_, compound) = binary::read(&mut &input[..], Java).unwrap();
+    assert_eq!(
+        compound.get("l"),
+        Some(&Value::from(List::Short(vec![256, -1])))
+    );
+}
+
+#[test]
+fn reject_invalid_kinds_and_truncated_input() {
+    use std::error::Error;
+
+    // The invalid tag IDs of an entry and of the elements of a list.
+    for input in [
+        &b"\x0a\x00\x00\x0d\x00\x00\x00"[..],
+        b"\x0a\x00\x00\x09\x00\x00\x20",
+    ] {
+        let error = binary::read(&mut &input[..], Java).unwrap_err();
+        assert!(matches!(error, NbtError::Kind(_)));
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "cannot convert from `u8` into `Kind`, value out of range"
+        );
+    }
+
+    for len in 0..ALL_KINDS.len() {
+        let error = binary::read(&mut &ALL_KINDS[..len], Java).unwrap_err();
+        assert!(matches!(error, NbtError::UnexpectedEof), "{len}: {error}");
+    }
+}
+
+#[test]
+fn reject_deep_nesting() {
+    let mut input = vec![0x0a, 0x00, 0x00];
+    for _ in 0..NbtError::MAX_DEPTH {
+        input.extend([0x0a, 0x00, 0x00]);
+    }
+    let error = binary::read(&mut &input[..], Java).unwrap_err();
+    assert!(matches!(error, NbtError::DepthLimit));
+}
+
+#[test]
+fn reject_long_string() {
+    let long = "x".repeat(usize::from(u16::MAX) + 1);
+    let error = binary::write(&mut Vec::new(), &long, &Compound::new(), Java).unwrap_err();
+    assert!(matches!(error, NbtError::LengthOverflow(65536)));
+}
+
+#[test]
+fn bedrock_is_little_endian() {
+    let input =
+        b"\x0a\x00\x00\x03\x01\x00i\x01\x00\x00\x00\x09\x01\x00l\x02\x01\x00\x00\x00\x03\x00\x00";
+    let (name, compound) = binary::read(&mut &input[..], Bedrock).unwrap();
+    assert_eq!(name, "");
+    assert_eq!(compound.get("i"), Some(&Value::Int(1)));
+    assert_eq!(compound.get("l"), Some(&Value::from(List::Short(vec![3]))));
+
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Bedrock).unwrap();
+    assert_eq!(bytes, input);
+}
+
+#[test]
+fn bedrock_round_trip() {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "root", &all_kinds(), Bedrock).unwrap();
+    assert_ne!(bytes, ALL_KINDS);
+    let (name, compound) = binary::read(&mut &bytes[..], Bedrock).unwrap();
+    assert_eq!(name, "root");
+    assert_eq!(compound, all_kinds());
+}
+
+#[test]
+fn bedrock_network_uses_var_ints() {
+    let input = b"\x0a\x00\x03\x01i\x01\x08\x01s\x02hi\x09\x01l\x04\x04\x80\x01\xff\x01\x00";
+    let (name, compound) = binary::read(&mut &input[..], BedrockNetwork).unwrap();
+    assert_eq!(name, "");
+    assert_eq!(compound.get("i"), Some(&Value::Int(-1)));
+    assert_eq!(compound.get("s"), Some(&Value::from("hi")));
+    assert_eq!(
+        compound.get("l"),
+        Some(&Value::from(List::Long(vec![64, -128])))
+    );
+
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, BedrockNetwork).unwrap();
+    assert_eq!(bytes, input);
+
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "root", &all_kinds(), BedrockNetwork).unwrap();
+    let (_, compound) = binary::read(&mut &bytes[..], BedrockNetwork).unwrap();
+    assert_eq!(compound, all_kinds());
+}
+
+#[test]
+fn bedrock_network_rejects_long_var_ints() {
+    let input = b"\x0a\x00\x03\x01i\xff\xff\xff\xff\x7f\x00";
+    let error = binary::read(&mut &input[..], BedrockNetwork).unwrap_err();
+    assert!(matches!(error, NbtError::InvalidVarInt));
+}
+
+#[test]
+fn nameless_root() {
+    let input = b"\x0a\x08\x00\x04name\x00\x09Bananrama\x00";
+    let value = binary::read_nameless(&mut &input[..], Java).unwrap();
+    let compound = value.as_compound().unwrap();
+    assert_eq!(compound.get("name"), Some(&Value::from("Bananrama")));
+
+    let mut bytes = Vec::new();
+    binary::write_nameless(&mut bytes, &value, Java).unwrap();
+    assert_eq!(bytes, input);
+
+    let input = b"\x08\x00\x02hi";
+    let value = binary::read_nameless(&mut &input[..], Java).unwrap();
+    assert_eq!(value, Value::from("hi"));
+
+    let mut bytes = Vec::new();
+    binary::write_nameless(&mut bytes, &value, Java).unwrap();
+    assert_eq!(bytes, input);
+
+    let error = binary::read_nameless(&mut &b"\x00"[..], Java).unwrap_err();
+    assert!(matches!(
+        error,
+        NbtError::UnexpectedKind { found: None, .. }
+    ));
+}
+
+#[test]
+fn java_strings_are_modified_utf8() {
+    let mut compound = Compound::new();
+    compound.insert("name", "\u{1F600}\0");
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Java).unwrap();
+    assert_eq!(
+        &bytes[10..],
+        b"\x00\x08\xed\xa0\xbd\xed\xb8\x80\xc0\x80\x00"
+    );
+    let (_, decoded) = binary::read(&mut &bytes[..], Java).unwrap();
+    assert_eq!(decoded, compound);
+
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Bedrock).unwrap();
+    assert_eq!(&bytes[10..], b"\x05\x00\xf0\x9f\x98\x80\x00\x00");
+}
+
+#[test]
+fn lossy_strings() {
+    let input = b"\x0a\x00\x00\x08\x00\x01s\x00\x02a\xff\x00";
+    let error = binary::read(&mut &input[..], Java).unwrap_err();
+    assert!(matches!(error, NbtError::InvalidString));
+    let (_, compound) = binary::read(&mut &input[..], Lossy(Java)).unwrap();
+    assert_eq!(compound.get("s"), Some(&Value::from("a\u{FFFD}")));
+}
+
+#[test]
+fn mixed_lists_are_wrapped() {
+    let mut list = List::Int(vec![1]);
+    list.push_mixed("a");
+    list.push_mixed(Compound::from_iter([("b", 2i8)]));
+    list.push_mixed(Compound::from_iter([("", 3)]));
+    assert!(list.is_mixed());
+    assert_eq!(list.kind(), Some(Kind::Compound));
+    assert_eq!(list.len(), 4);
+    let compound = Compound::from_iter([("m", list.clone())]);
+
+    #[rustfmt::skip]
+    let expected: &[u8] = &[
+        0x0a, 0x00, 0x00,
+        0x09, 0x00, 0x01, b'm', 0x0a, 0x00, 0x00, 0x00, 0x04,
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x08, 0x00, 0x00, 0x00, 0x01, b'a', 0x00,
+            0x01, 0x00, 0x01, b'b', 0x02, 0x00,
+            0x0a, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+        0x00,
+    ];
+    let bytes = encode("", &compound);
+    assert_eq!(bytes, expected);
+
+    let (_, read) = binary::read(&mut &bytes[..], Java).unwrap();
+    assert_eq!(read.get("m"), Some(&Value::from(list)));
+}
+
+#[test]
+fn mixed_lists_from_values() {
+    let list = List::from_values(vec![Value::Int(1), Value::Int(2)]);
+    assert_eq!(list, List::Int(vec![1, 2]));
+    let list = List::from_values(vec![Value::Int(1), Value::Long(2)]);
+    assert_eq!(list, List::Mixed(vec![Value::Int(1), Value::Long(2)]));
+    assert_eq!(list.get(1), Some(ValueRef::Long(2)));
+    assert_eq!(List::from_values(Vec::new()), List::End);
+
+    // A list of compounds without any wrapper is not mixed.
+    let plain = List::Compound(vec![Compound::from_iter([("a", 1)])]);
+    let compound = Compound::from_iter([("p", plain.clone())]);
+    let (_, read) = binary::read(&mut &encode("", &compound)[..], Java).unwrap();
+    assert_eq!(read.get("p"), Some(&Value::from(plain)));
+}
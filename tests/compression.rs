@@ -0,0 +1,74 @@
+#![cfg(feature = "compression")]
+
+use znbt::binary::{self, Java};
+use znbt::compression::{self, Compression};
+use znbt::error::NbtError;
+use znbt::value::Compound;
+
+fn compound() -> Compound {
+    Compound::from_iter([("name", "Bananrama")])
+}
+
+#[test]
+fn detect_magic_bytes() {
+    assert_eq!(Compression::detect(b"\x1f\x8b\x08"), Compression::Gzip);
+    assert_eq!(Compression::detect(b"\x78\x9c"), Compression::Zlib);
+    assert_eq!(Compression::detect(b"\x78\x00"), Compression::Uncompressed);
+    assert_eq!(Compression::detect(b"\x0a\x00"), Compression::Uncompressed);
+    assert_eq!(Compression::detect(b""), Compression::Uncompressed);
+}
+
+#[test]
+fn round_trip() {
+    for compression in [
+        Compression::Uncompressed,
+        Compression::Gzip,
+        Compression::Zlib,
+    ] {
+        let mut bytes = Vec::new();
+        compression::write(&mut bytes, "root", &compound(), Java, compression).unwrap();
+        assert_eq!(Compression::detect(&bytes), compression);
+
+        let (name, decoded) = compression::read(&bytes[..], Java).unwrap();
+        assert_eq!(name, "root");
+        assert_eq!(decoded, compound());
+    }
+}
+
+#[test]
+fn compress_then_decompress() {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound(), Java).unwrap();
+    let compressed = compression::compress(&bytes, Compression::Gzip).unwrap();
+    assert_eq!(*compression::decompress(&compressed).unwrap(), *bytes);
+}
+
+#[test]
+fn limit_decompressed_len() {
+    let bytes = vec![0; 1 << 20];
+    for compression in [Compression::Gzip, Compression::Zlib] {
+        let compressed = compression::compress(&bytes, compression).unwrap();
+        assert!(compressed.len() < 4096);
+        let error = compression::decompress_with_limit(&compressed, bytes.len() - 1).unwrap_err();
+        assert!(matches!(error, NbtError::SizeLimit(_)), "{error}");
+        let decompressed = compression::decompress_with_limit(&compressed, bytes.len()).unwrap();
+        assert_eq!(decompressed.len(), bytes.len());
+    }
+    // The uncompressed bytes are not limited, since they are already read.
+    assert!(compression::decompress_with_limit(&bytes, 0).is_ok());
+}
+
+#[test]
+fn decompress_gzip_members() {
+    let mut bytes = compression::compress(b"\x0a\x00", Compression::Gzip)
+        .unwrap()
+        .into_owned();
+    bytes.extend_from_slice(&compression::compress(b"\x00\x00", Compression::Gzip).unwrap());
+    assert_eq!(
+        *compression::decompress(&bytes).unwrap(),
+        *b"\x0a\x00\x00\x00"
+    );
+    let (name, decoded) = compression::read(&bytes[..], Java).unwrap();
+    assert_eq!(name, "");
+    assert!(decoded.is_empty());
+}
@@ -0,0 +1,398 @@
+use znbt::diff::{self, Change, Patch};
+use znbt::path::NbtPath;
+use znbt::value::{Compound, List, Value};
+
+fn compound(input: &str) -> Compound {
+    input.parse().unwrap()
+}
+
+fn path(input: &str) -> NbtPath {
+    input.parse().unwrap()
+}
+
+fn old() -> Compound {
+    compound(
+        r#"{
+            DataVersion: 3953,
+            Pos: [1.0d, 64.0d, 2.0d],
+            Inventory: [
+                {Slot: 0b, id: "minecraft:stone", count: 1},
+                {Slot: 1b, id: "minecraft:dirt", count: 2},
+            ],
+            Tags: ["a", "b", "c"],
+            UUID: [I; 1, 2, 3, 4],
+            abilities: {flying: 0b, walkSpeed: 0.1f},
+        }"#,
+    )
+}
+
+fn new() -> Compound {
+    compound(
+        r#"{
+            DataVersion: 3953L,
+            Pos: [1.0d, 65.0d, 2.0d],
+            Inventory: [
+                {Slot: 0b, id: "minecraft:stone", count: 64},
+                {Slot: 1b, id: "minecraft:dirt", count: 2},
+                {Slot: 2b, id: "minecraft:sand", count: 1},
+            ],
+            Tags: ["a"],
+            UUID: [I; 1, 2, 3, 5],
+            abilities: {walkSpeed: 0.1f, mayfly: 1b},
+            Health: 20.0f,
+        }"#,
+    )
+}
+
+#[test]
+fn diff_trees() {
+    let patch = diff::diff(&old(), &new());
+    assert_eq!(
+        patch.changes(),
+        [
+            Change::Replace {
+                path: path("DataVersion"),
+                old: Value::Int(3953),
+                new: Value::Long(3953),
+            },
+            Change::Replace {
+                path: path("Pos[1]"),
+                old: Value::Double(64.0),
+                new: Value::Double(65.0),
+            },
+            Change::Replace {
+                path: path("Inventory[0].count"),
+                old: Value::Int(1),
+                new: Value::Int(64),
+            },
+            Change::Add {
+                path: path("Inventory[2]"),
+                value: compound(r#"{Slot: 2b, id: "minecraft:sand", count: 1}"#).into(),
+            },
+            Change::Remove {
+                path: path("Tags[2]"),
+                value: Value::from("c"),
+            },
+            Change::Remove {
+                path: path("Tags[1]"),
+                value: Value::from("b"),
+            },
+            Change::Replace {
+                path: path("UUID"),
+                old: Value::IntArray([1, 2, 3, 4].into()),
+                new: Value::IntArray([1, 2, 3, 5].into()),
+            },
+            Change::Remove {
+                path: path("abilities.flying"),
+                value: Value::Byte(0),
+            },
+            Change::Add {
+                path: path("abilities.mayfly"),
+                value: Value::Byte(1),
+            },
+            Change::Add {
+                path: path("Health"),
+                value: Value::Float(20.0),
+            },
+        ]
+    );
+    let kinds: Vec<_> = patch.changes().iter().map(Change::is_kind_change).collect();
+    assert_eq!(kinds.iter().filter(|&&kind| kind).count(), 1);
+    assert!(kinds[0]);
+
+    assert!(diff::diff(&old(), &old()).is_empty());
+    assert_eq!(diff::diff(&Compound::new(), &old()).len(), old().len());
+}
+
+#[test]
+fn diff_lists_of_other_kinds() {
+    let old = compound(r#"{a: [1, 2], b: [], c: [[1], [2b]]}"#);
+    let new = compound(r#"{a: [1L, 2L], b: [1], c: [[1], [3b]]}"#);
+    assert_eq!(
+        diff::diff(&old, &new).changes(),
+        [
+            Change::Replace {
+                path: path("a"),
+                old: List::Int(vec![1, 2]).into(),
+                new: List::Long(vec![1, 2]).into(),
+            },
+            Change::Replace {
+                path: path("b"),
+                old: List::End.into(),
+                new: List::Int(vec![1]).into(),
+            },
+            Change::Replace {
+                path: path("c[1][0]"),
+                old: Value::Byte(2),
+                new: Value::Byte(3),
+            },
+        ]
+    );
+}
+
+#[test]
+fn apply_patch() {
+    let patch = diff::diff(&old(), &new());
+    let mut tree = old();
+    patch.apply(&mut tree).unwrap();
+    assert_eq!(tree, new());
+
+    // The patch no longer applies to the new tree, which is left unchanged.
+    let error = patch.apply(&mut tree).unwrap_err();
+    assert_eq!(error.index(), 0);
+    assert_eq!(error.to_string(), "value mismatch at change 0");
+    assert_eq!(tree, new());
+
+    // The order of the entries is not compared.
+    let reverse = diff::diff(&new(), &old());
+    reverse.apply(&mut tree).unwrap();
+    assert_ne!(tree, old());
+    assert!(diff::diff(&tree, &old()).is_empty());
+}
+
+#[test]
+fn compare_floats_bitwise() {
+    let mut tree = compound("{a: 1.0f, l: [1.0d], c: {x: 0.0f}}");
+    tree.insert("a", f32::NAN);
+    tree.insert("l", List::Double(vec![f64::NAN]));
+    tree.insert("c", Compound::from_iter([("x", f32::NAN)]));
+    assert!(diff::diff(&tree, &tree.clone()).is_empty());
+
+    let mut new = tree.clone();
+    new.insert("a", 1.0f32);
+    new.insert("b", -0.0f64);
+    let patch = diff::diff(&tree, &new);
+    assert_eq!(patch.len(), 2);
+    assert_eq!(patch.changes()[1].to_string(), "+ b: -0.0d");
+    let mut result = tree.clone();
+    patch.apply(&mut result).unwrap();
+    assert!(diff::diff(&result, &new).is_empty());
+
+    // A NaN in a removed or replaced value is matched against the tree.
+    let patch = Patch::from_iter([
+        Change::Remove {
+            path: path("c"),
+            value: Compound::from_iter([("x", f32::NAN)]).into(),
+        },
+        Change::Replace {
+            path: path("l[0]"),
+            old: Value::Double(f64::NAN),
+            new: Value::Double(0.0),
+        },
+    ]);
+    patch.apply(&mut tree).unwrap();
+    assert_eq!(tree.get("l"), Some(&Value::from(List::Double(vec![0.0]))));
+    assert!(tree.get("c").is_none());
+
+    // The zeros of different signs are different.
+    let mut zero = new.clone();
+    zero.insert("b", 0.0f64);
+    assert_eq!(diff::diff(&new, &zero).len(), 1);
+}
+
+#[test]
+fn reject_inapplicable_changes() {
+    let tree = compound(r#"{a: {b: 1}, l: [1, 2], s: "x"}"#);
+    for (change, message) in [
+        (
+            Change::Add {
+                path: path("a.b"),
+                value: Value::Int(2),
+            },
+            "value mismatch",
+        ),
+        (
+            Change::Add {
+                path: path("l[1]"),
+                value: Value::Int(3),
+            },
+            "value mismatch",
+        ),
+        (
+            Change::Add {
+                path: path("l[2]"),
+                value: Value::Long(3),
+            },
+            "kind mismatch",
+        ),
+        (
+            Change::Replace {
+                path: path("l[0]"),
+                old: Value::Int(1),
+                new: Value::from("y"),
+            },
+            "kind mismatch",
+        ),
+        (
+            Change::Remove {
+                path: path("x.b"),
+                value: Value::Int(1),
+            },
+            "missing parent",
+        ),
+        (
+            Change::Remove {
+                path: path("s.b"),
+                value: Value::Int(1),
+            },
+            "expected compound",
+        ),
+        (
+            Change::Remove {
+                path: path("a[0]"),
+                value: Value::Int(1),
+            },
+            "expected list",
+        ),
+        (
+            Change::Remove {
+                path: path("l[-1]"),
+                value: Value::Int(2),
+            },
+            "unsupported path",
+        ),
+        (
+            Change::Remove {
+                path: path("l[-1].x"),
+                value: Value::Int(2),
+            },
+            "unsupported path",
+        ),
+        (
+            Change::Remove {
+                path: path("l[].x"),
+                value: Value::Int(2),
+            },
+            "unsupported path",
+        ),
+        (
+            Change::Remove {
+                path: path(""),
+                value: Value::Int(2),
+            },
+            "empty path",
+        ),
+    ] {
+        let patch = Patch::from_iter([
+            Change::Add {
+                path: path("new"),
+                value: Value::Int(0),
+            },
+            change,
+        ]);
+        let mut result = tree.clone();
+        let error = patch.apply(&mut result).unwrap_err();
+        assert_eq!(error.to_string(), format!("{message} at change 1"));
+        assert_eq!(result, tree);
+    }
+}
+
+#[test]
+fn display_patch() {
+    let patch = diff::diff(
+        &compound(r#"{a: 1, b: {"c d": [1b]}}"#),
+        &compound(r#"{a: 1L, b: {"c d": [1b, 2b]}, e: "x"}"#),
+    );
+    assert_eq!(
+        patch.to_string(),
+        "~ a: 1 -> 1L\n+ b.\"c d\"[1]: 2b\n+ e: \"x\"\n"
+    );
+    assert_eq!(Patch::new().to_string(), "");
+}
+
+#[test]
+fn convert_patch_to_compound() {
+    let patch = diff::diff(&old(), &new());
+    let compound = patch.to_compound();
+    let Some(Value::List(changes)) = compound.get("changes") else {
+        panic!("expected changes");
+    };
+    assert_eq!(changes.len(), patch.len());
+    assert_eq!(
+        changes.get(0),
+        Some(
+            Value::from(crate::compound(
+                r#"{op: "replace", path: "DataVersion", old: 3953, new: 3953L}"#
+            ))
+            .as_borrowed()
+        )
+    );
+    assert_eq!(Patch::from_compound(&compound).unwrap(), patch);
+    assert_eq!(
+        Patch::from_compound(&Patch::new().to_compound()).unwrap(),
+        Patch::new()
+    );
+
+    for (input, index, message) in [
+        ("{}", 0, "expected changes"),
+        ("{changes: [1]}", 0, "expected compound"),
+        (
+            r#"{changes: [{op: "add", path: "a", value: 1}, {path: "a"}]}"#,
+            1,
+            "expected op",
+        ),
+        (r#"{changes: [{op: "move", path: "a"}]}"#, 0, "unknown op"),
+        (r#"{changes: [{op: "add", value: 1}]}"#, 0, "expected path"),
+        (
+            r#"{changes: [{op: "add", path: "a[", value: 1}]}"#,
+            0,
+            "invalid path",
+        ),
+        (
+            r#"{changes: [{op: "replace", path: "a", old: 1}]}"#,
+            0,
+            "missing value",
+        ),
+    ] {
+        let error = Patch::from_compound(&crate::compound(input)).unwrap_err();
+        assert_eq!(error.index(), index, "{input}");
+        assert_eq!(error.to_string(), format!("{message} at change {index}"));
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn convert_patch_to_json() {
+    let patch = diff::diff(&old(), &new());
+    let json = serde_json::to_string(&patch).unwrap();
+    assert!(
+        json.starts_with(r#"[{"op":"replace","path":"DataVersion","old":"3953","new":"3953L"},"#)
+    );
+    assert!(json.contains(r#"{"op":"remove","path":"Tags[2]","value":"\"c\""}"#));
+    assert_eq!(serde_json::from_str::<Patch>(&json).unwrap(), patch);
+
+    // A mixed list is printed unwrapped and read back by the 1.21.5 dialect.
+    let mut mixed = List::Int(vec![1]);
+    mixed.push_mixed("a");
+    mixed.push_mixed(Compound::from_iter([("b", 2i64)]));
+    let patch = diff::diff(
+        &Compound::new(),
+        &Compound::from_iter([("m", Value::from(mixed))]),
+    );
+    let json = serde_json::to_string(&patch).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"op":"add","path":"m","value":"[1,\"a\",{b:2L}]"}]"#
+    );
+    assert_eq!(serde_json::from_str::<Patch>(&json).unwrap(), patch);
+
+    // The non-finite floats cannot be printed as SNBT.
+    for value in [
+        Value::Float(f32::NAN),
+        Value::Double(f64::NEG_INFINITY),
+        List::Float(vec![f32::INFINITY]).into(),
+        Compound::from_iter([("x", f64::NAN)]).into(),
+    ] {
+        let patch = Patch::from_iter([Change::Add {
+            path: path("a"),
+            value,
+        }]);
+        let error = serde_json::to_string(&patch).unwrap_err();
+        assert_eq!(error.to_string(), "non-finite float in SNBT");
+    }
+
+    let error = serde_json::from_str::<Patch>(r#"[{"op":"add","path":"a","value":"{"}]"#);
+    assert!(error.is_err());
+    let error = serde_json::from_str::<Patch>(r#"[{"op":"add","path":"a["}]"#);
+    assert!(error.is_err());
+}
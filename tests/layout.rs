@@ -0,0 +1,52 @@
+//! Guards the memory layout of the value tree, which is the main promise of
+//! this crate.
+
+#![cfg(target_pointer_width = "64")]
+
+use std::mem::size_of;
+
+use znbt::value::{ByteArray, Compound, IntArray, List, LongArray, ThinStr, Value};
+
+#[test]
+fn value_is_16_bytes() {
+    assert_eq!(size_of::<Value>(), 16);
+    assert_eq!(size_of::<Option<Value>>(), 16);
+}
+
+#[test]
+fn heap_payloads_are_thin() {
+    assert_eq!(size_of::<ThinStr>(), 8);
+    assert_eq!(size_of::<ByteArray>(), 8);
+    assert_eq!(size_of::<IntArray>(), 8);
+    assert_eq!(size_of::<LongArray>(), 8);
+    assert_eq!(size_of::<Option<ThinStr>>(), 8);
+}
+
+#[test]
+fn containers_are_compact() {
+    assert_eq!(size_of::<List>(), 32);
+    assert_eq!(size_of::<Compound>(), 24);
+    assert_eq!(size_of::<(ThinStr, Value)>(), 24);
+}
+
+#[test]
+fn list_elements_are_unboxed() {
+    let list = List::try_from(vec![Value::Int(1), Value::Int(2)]).unwrap();
+    assert_eq!(list, List::Int(vec![1, 2]));
+}
+
+#[test]
+fn thin_slices_round_trip() {
+    let empty = ByteArray::new();
+    assert!(empty.is_empty());
+    assert_eq!(empty.clone(), ByteArray::from(vec![]));
+
+    let mut longs = LongArray::from(vec![1, -2, i64::MAX]);
+    longs[1] = 7;
+    assert_eq!(&*longs, &[1, 7, i64::MAX]);
+    assert_eq!(longs.clone(), longs);
+
+    let string = ThinStr::from("minecraft:stone");
+    assert_eq!(string, "minecraft:stone");
+    assert_eq!(String::from(string), "minecraft:stone");
+}
@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+
+use znbt::mutf8;
+
+#[test]
+fn ascii_is_borrowed() {
+    assert!(matches!(
+        mutf8::decode(b"stone"),
+        Ok(Cow::Borrowed("stone"))
+    ));
+    assert!(matches!(mutf8::encode("stone"), Cow::Borrowed(b"stone")));
+}
+
+#[test]
+fn null_is_two_bytes() {
+    assert_eq!(&*mutf8::encode("a\0b"), b"a\xc0\x80b");
+    assert_eq!(mutf8::decode(b"a\xc0\x80b").unwrap(), "a\0b");
+    assert_eq!(mutf8::encoded_len("a\0b"), 4);
+}
+
+#[test]
+fn supplementary_is_surrogate_pair() {
+    // U+1F600 is the surrogate pair U+D83D U+DE00.
+    let encoded = b"x\xed\xa0\xbd\xed\xb8\x80";
+    assert_eq!(&*mutf8::encode("x\u{1F600}"), encoded);
+    assert_eq!(mutf8::decode(encoded).unwrap(), "x\u{1F600}");
+    assert_eq!(mutf8::encoded_len("x\u{1F600}"), encoded.len());
+}
+
+#[test]
+fn utf8_is_accepted() {
+    assert_eq!(
+        mutf8::decode("é\u{1F600}".as_bytes()).unwrap(),
+        "é\u{1F600}"
+    );
+    assert_eq!(
+        mutf8::decode(b"\xc0\x80\xf0\x9f\x98\x80").unwrap(),
+        "\0\u{1F600}"
+    );
+}
+
+#[test]
+fn ill_formed_is_rejected_or_replaced() {
+    // An unpaired high surrogate, an unpaired low surrogate and a stray byte.
+    let input = b"a\xed\xa0\xbdb\xed\xb8\x80\xff";
+    assert!(mutf8::decode(input).is_err());
+    assert_eq!(mutf8::decode_lossy(input), "a\u{FFFD}b\u{FFFD}\u{FFFD}");
+}
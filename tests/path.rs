@@ -0,0 +1,322 @@
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::path::{NbtPath, Node};
+use znbt::value::{Compound, List, MergeMode, Value, ValueRef};
+
+fn path(input: &str) -> NbtPath {
+    input.parse().unwrap()
+}
+
+fn compound(input: &str) -> Compound {
+    input.parse().unwrap()
+}
+
+fn chest() -> Compound {
+    compound(
+        r#"{
+            id: "minecraft:chest",
+            Items: [
+                {Slot: 0b, id: "minecraft:stone", count: 1, tag: {Lore: ["a", "b"]}},
+                {Slot: 1b, id: "minecraft:dirt", count: 2},
+                {Slot: 2b, id: "minecraft:stone", count: 3},
+            ],
+            Pos: [1.0d, 2.0d, 3.0d],
+            UUID: [I; 1, 2, 3, 4],
+        }"#,
+    )
+}
+
+#[test]
+fn parse_nodes() {
+    let filter = compound(r#"{id: "minecraft:stone"}"#);
+    assert_eq!(
+        path(r#"a.b[0].c"#).nodes(),
+        [
+            Node::Key("a".into()),
+            Node::Key("b".into()),
+            Node::Index(0),
+            Node::Key("c".into()),
+        ]
+    );
+    assert_eq!(
+        path(r#"Items[{id:"minecraft:stone"}]"#).nodes(),
+        [
+            Node::Key("Items".into()),
+            Node::MatchElement(filter.clone())
+        ]
+    );
+    assert_eq!(
+        path(r#"{Count:1b}.Items[].tag{a:1}[-1]"#).nodes(),
+        [
+            Node::MatchRoot(compound("{Count: 1b}")),
+            Node::Key("Items".into()),
+            Node::All,
+            Node::MatchKey("tag".into(), compound("{a: 1}")),
+            Node::Index(-1),
+        ]
+    );
+    assert_eq!(
+        path(r#""display name".'it"s'.minecraft:custom_name.[0]"#).nodes(),
+        [
+            Node::Key("display name".into()),
+            Node::Key("it\"s".into()),
+            Node::Key("minecraft:custom_name".into()),
+            Node::Index(0),
+        ]
+    );
+    assert_eq!(path("").nodes(), []);
+    assert_eq!(
+        path("ü.ß").nodes(),
+        [Node::Key("ü".into()), Node::Key("ß".into())]
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    for (input, expected) in [
+        ("a.b[0].c", "a.b[0].c"),
+        (
+            r#"Items[{id:"minecraft:stone"}]"#,
+            r#"Items[{id:"minecraft:stone"}]"#,
+        ),
+        (
+            "{Count:1b}.Items[].tag{a:1}[-1]",
+            "{Count:1b}.Items[].tag{a:1}[-1]",
+        ),
+        (
+            r#""display name".minecraft:id"#,
+            r#""display name"."minecraft:id""#,
+        ),
+        ("a.[0][1]", "a[0][1]"),
+        ("", ""),
+    ] {
+        let path = path(input);
+        assert_eq!(path.to_string(), expected);
+        assert_eq!(expected.parse::<NbtPath>().unwrap(), path);
+    }
+}
+
+#[test]
+fn reject_malformed_paths() {
+    for (input, position) in [
+        ("a..b", 2),
+        (".a", 0),
+        ("a b", 1),
+        ("a[x]", 2),
+        ("a[0", 3),
+        ("a[99999999999]", 2),
+        ("a[0]{b:1}", 4),
+        ("a{b:}", 4),
+        ("a.b{c:1}d", 8),
+        ("{a:1}b", 5),
+        ("\"a", 0),
+        ("a]", 1),
+    ] {
+        let error = input.parse::<NbtPath>().unwrap_err();
+        assert_eq!(error.position(), position, "{input}: {error}");
+    }
+}
+
+#[test]
+fn get_values() {
+    let chest = chest();
+    assert_eq!(
+        path("id").get(&chest),
+        [ValueRef::String("minecraft:chest")]
+    );
+    assert_eq!(path("Items[1].count").get(&chest), [ValueRef::Int(2)]);
+    assert_eq!(path("Items[-1].Slot").get(&chest), [ValueRef::Byte(2)]);
+    assert_eq!(path("Items[3]").get(&chest), []);
+    assert_eq!(path("Items[-4]").get(&chest), []);
+    assert_eq!(
+        path("Items[].count").get(&chest),
+        [ValueRef::Int(1), ValueRef::Int(2), ValueRef::Int(3)]
+    );
+    assert_eq!(
+        path(r#"Items[{id: "minecraft:stone"}].Slot"#).get(&chest),
+        [ValueRef::Byte(0), ValueRef::Byte(2)]
+    );
+    assert_eq!(
+        path(r#"Items[{tag: {Lore: ["b"]}}].Slot"#).get(&chest),
+        [ValueRef::Byte(0)]
+    );
+    assert_eq!(path(r#"Items[{tag: {Lore: []}}]"#).get(&chest), []);
+    assert_eq!(
+        path("Items[0].tag{Lore: [\"a\"]}.Lore[1]").get(&chest),
+        [ValueRef::String("b")]
+    );
+    assert_eq!(path("Items[0].tag{x: 1}").get(&chest), []);
+    assert_eq!(path("Pos[1]").get(&chest), [ValueRef::Double(2.0)]);
+    assert_eq!(path("UUID[-1]").get(&chest), [ValueRef::Int(4)]);
+    assert_eq!(path("UUID[]").get(&chest).len(), 4);
+    assert_eq!(path("id.x").get(&chest), []);
+    assert_eq!(path("id[0]").get(&chest), []);
+
+    assert_eq!(
+        path(r#"{id: "minecraft:chest"}.Pos[0]"#).get(&chest),
+        [ValueRef::Double(1.0)]
+    );
+    assert_eq!(path(r#"{id: "minecraft:barrel"}.Pos[0]"#).get(&chest), []);
+    assert_eq!(path("").get(&chest), [ValueRef::Compound(&chest)]);
+}
+
+#[test]
+fn set_values() {
+    let mut chest = chest();
+    assert_eq!(path("Items[].count").set(&mut chest, &Value::Int(2)), 2);
+    assert_eq!(path("Items[].count").get(&chest), [ValueRef::Int(2); 3]);
+    assert_eq!(path("Items[].count").set(&mut chest, &Value::Int(2)), 0);
+
+    let stone = path(r#"Items[{id: "minecraft:stone"}].count"#);
+    assert_eq!(stone.set(&mut chest, &Value::Int(64)), 2);
+    assert_eq!(path("Items[0].count").get(&chest), [ValueRef::Int(64)]);
+    assert_eq!(path("Items[1].count").get(&chest), [ValueRef::Int(2)]);
+
+    // The elements of a typed list are only set to values of the kind.
+    assert_eq!(path("Pos[0]").set(&mut chest, &Value::Int(0)), 0);
+    assert_eq!(path("Pos[-1]").set(&mut chest, &Value::Double(0.0)), 1);
+    assert_eq!(path("Pos[]").get(&chest)[2], ValueRef::Double(0.0));
+    assert_eq!(path("UUID[1]").set(&mut chest, &Value::Int(0)), 1);
+    assert_eq!(path("UUID[]").set(&mut chest, &Value::Long(0)), 0);
+    assert_eq!(
+        path("UUID").get(&chest),
+        [ValueRef::IntArray(&[1, 0, 3, 4])]
+    );
+
+    // The missing parents are created.
+    let mut root = Compound::new();
+    assert_eq!(path("a.b.c").set(&mut root, &Value::Int(1)), 1);
+    assert_eq!(path("l[].x").set(&mut root, &Value::Int(2)), 1);
+    assert_eq!(path(r#"m[{id: "x"}].n"#).set(&mut root, &Value::Int(3)), 1);
+    assert_eq!(
+        path(r#"m[{id: "y"}]"#).set(&mut root, &Value::from(compound("{id: \"y\"}"))),
+        0
+    );
+    assert_eq!(path("k{id: 1}.v").set(&mut root, &Value::Int(4)), 1);
+    assert_eq!(path("e[]").set(&mut root, &Value::Int(5)), 1);
+    assert_eq!(path("i[0]").set(&mut root, &Value::Int(6)), 0);
+    assert_eq!(
+        root,
+        compound(
+            r#"{
+                a: {b: {c: 1}},
+                l: [{x: 2}],
+                m: [{id: "x", n: 3}],
+                k: {id: 1, v: 4},
+                e: [5],
+                i: [],
+            }"#
+        )
+    );
+
+    // The root cannot be set.
+    assert_eq!(path("").set(&mut root, &Value::Int(0)), 0);
+    assert_eq!(path("{}").set(&mut root, &Value::Int(0)), 0);
+}
+
+#[test]
+fn remove_values() {
+    let mut chest = chest();
+    assert_eq!(path("Items[].tag.Lore[0]").remove(&mut chest), 1);
+    assert_eq!(
+        path("Items[0].tag.Lore").get(&chest),
+        [ValueRef::List(&List::String(vec!["b".into()]))]
+    );
+    assert_eq!(
+        path(r#"Items[{id: "minecraft:stone"}]"#).remove(&mut chest),
+        2
+    );
+    assert_eq!(path("Items[].Slot").get(&chest), [ValueRef::Byte(1)]);
+    assert_eq!(path("Items[0].count{}").remove(&mut chest), 0);
+    assert_eq!(path("Items[-1].missing").remove(&mut chest), 0);
+    assert_eq!(path("Items[{}].id").remove(&mut chest), 1);
+    assert_eq!(path("Pos[]").remove(&mut chest), 3);
+    assert_eq!(path("Pos").get(&chest), [ValueRef::List(&List::End)]);
+    assert_eq!(path("UUID[-2]").remove(&mut chest), 1);
+    assert_eq!(path("UUID").get(&chest), [ValueRef::IntArray(&[1, 2, 4])]);
+    assert_eq!(path("UUID[3]").remove(&mut chest), 0);
+    assert_eq!(path("UUID[]").remove(&mut chest), 3);
+    assert_eq!(path(r#"{id: "minecraft:barrel"}.id"#).remove(&mut chest), 0);
+    assert_eq!(path(r#"{id: "minecraft:chest"}.id"#).remove(&mut chest), 1);
+    assert_eq!(path("missing.x").remove(&mut chest), 0);
+    assert!(chest.get("missing").is_none());
+    assert_eq!(
+        chest,
+        compound("{Items: [{Slot: 1b, count: 2}], Pos: [], UUID: [I;]}")
+    );
+}
+
+#[test]
+fn merge_compounds() {
+    let mut chest = chest();
+    let source = compound(r#"{tag: {Damage: 1}, count: 5}"#);
+    let stone = path(r#"Items[{id: "minecraft:stone"}]"#);
+    assert_eq!(stone.merge(&mut chest, &source).unwrap(), 2);
+    assert_eq!(stone.merge(&mut chest, &source).unwrap(), 0);
+    assert_eq!(
+        path("Items[0]").get(&chest),
+        [ValueRef::Compound(&compound(
+            r#"{Slot: 0b, id: "minecraft:stone", count: 5, tag: {Lore: ["a", "b"], Damage: 1}}"#
+        ))]
+    );
+
+    // The missing target is created.
+    assert_eq!(path("a.b").merge(&mut chest, &source).unwrap(), 1);
+    assert_eq!(path("a.b").get(&chest), [ValueRef::Compound(&source)]);
+    assert_eq!(path("").merge(&mut chest, &compound("{id: 1}")).unwrap(), 1);
+    assert_eq!(path("id").get(&chest), [ValueRef::Int(1)]);
+
+    let replace = compound("{tag: {Damage: 2}}");
+    let first = path("Items[0]");
+    assert_eq!(
+        first
+            .merge_with(&mut chest, &replace, MergeMode::Replace)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        path("Items[0].tag").get(&chest),
+        [ValueRef::Compound(
+            replace.get("tag").unwrap().as_compound().unwrap()
+        )]
+    );
+
+    let before = chest.clone();
+    let error = path("Items[].count")
+        .merge(&mut chest, &source)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: Some(Kind::Int)
+        }
+    ));
+    assert!(matches!(
+        path("Pos[0]").merge(&mut chest, &source),
+        Err(NbtError::UnexpectedKind {
+            found: Some(Kind::Double),
+            ..
+        })
+    ));
+    assert_eq!(chest, before);
+}
+
+#[test]
+fn list_set_and_remove() {
+    let mut list = List::Int(vec![1, 2, 3]);
+    assert_eq!(list.set(1, 5), Ok(Value::Int(2)));
+    assert_eq!(list.set(3, 5), Err(Value::Int(5)));
+    assert_eq!(list.set(0, 5i64), Err(Value::Long(5)));
+    assert_eq!(list.remove(0), Some(Value::Int(1)));
+    assert_eq!(list.remove(2), None);
+    assert_eq!(list, List::Int(vec![5, 3]));
+    list.remove(0);
+    list.remove(0);
+    assert_eq!(list, List::Int(Vec::new()));
+
+    let mut mixed = List::Mixed(vec![Value::Int(1), Value::from("a")]);
+    assert_eq!(mixed.set(0, "b"), Ok(Value::Int(1)));
+    assert_eq!(mixed.remove(1), Some(Value::from("a")));
+    assert_eq!(List::End.remove(0), None);
+}
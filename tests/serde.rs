@@ -0,0 +1,318 @@
+#![cfg(feature = "serde")]
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use znbt::binary::{self, Java};
+use znbt::de::Deserializer;
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::ser::Serializer;
+use znbt::value::{Compound, List, Value};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Player {
+    name: String,
+    health: f32,
+    level: i32,
+    on_ground: bool,
+    pos: [f64; 3],
+    inventory: Vec<Item>,
+    spawn: Option<(i32, i32, i32)>,
+    mode: Mode,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Item {
+    id: String,
+    count: u8,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Mode {
+    Survival,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape {
+    Circle(f32),
+    Point(i32, i32),
+    Rect { w: i16, h: i16 },
+}
+
+fn encode(compound: &Compound) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", compound, Java).unwrap();
+    bytes
+}
+
+fn player() -> Player {
+    Player {
+        name: "Steve".into(),
+        health: 20.0,
+        level: 3,
+        on_ground: true,
+        pos: [0.5, 64.0, -0.5],
+        inventory: vec![Item {
+            id: "minecraft:stone".into(),
+            count: 64,
+        }],
+        spawn: None,
+        mode: Mode::Survival,
+    }
+}
+
+#[test]
+fn serialize_struct() {
+    let mut compound = Compound::new();
+    compound.insert("name", "Steve");
+    compound.insert("health", 20.0f32);
+    compound.insert("level", 3);
+    compound.insert("on_ground", true);
+    compound.insert("pos", List::Double(vec![0.5, 64.0, -0.5]));
+    let item = Compound::from_iter([
+        ("id", Value::from("minecraft:stone")),
+        ("count", Value::from(64i8)),
+    ]);
+    compound.insert("inventory", List::Compound(vec![item]));
+    compound.insert("mode", "Survival");
+
+    assert_eq!(znbt::to_vec(&player()).unwrap(), encode(&compound));
+}
+
+#[test]
+fn serialize_map_and_empty_list() {
+    let map = BTreeMap::from([("a", vec![]), ("b", vec![1i64, 2])]);
+
+    let mut compound = Compound::new();
+    compound.insert("a", List::End);
+    compound.insert("b", List::Long(vec![1, 2]));
+
+    assert_eq!(znbt::to_vec(&map).unwrap(), encode(&compound));
+}
+
+#[test]
+fn serialize_enum_variants() {
+    let shapes = BTreeMap::from([
+        ("circle", Shape::Circle(1.0)),
+        ("point", Shape::Point(1, 2)),
+        ("rect", Shape::Rect { w: 3, h: 4 }),
+    ]);
+
+    let mut compound = Compound::new();
+    compound.insert("circle", Compound::from_iter([("Circle", 1.0f32)]));
+    let point = List::Int(vec![1, 2]);
+    compound.insert("point", Compound::from_iter([("Point", point)]));
+    let rect = Compound::from_iter([("w", 3i16), ("h", 4i16)]);
+    compound.insert("rect", Compound::from_iter([("Rect", rect)]));
+
+    assert_eq!(znbt::to_vec(&shapes).unwrap(), encode(&compound));
+}
+
+#[test]
+fn serialize_named_and_nameless_root() {
+    let mut bytes = Vec::new();
+    let serializer = Serializer::new(&mut bytes, Java).with_name("hello world");
+    BTreeMap::from([("name", "Bananrama")])
+        .serialize(serializer)
+        .unwrap();
+    assert_eq!(
+        bytes,
+        b"\x0a\x00\x0bhello world\x08\x00\x04name\x00\x09Bananrama\x00"
+    );
+
+    let mut bytes = Vec::new();
+    42i16
+        .serialize(Serializer::new(&mut bytes, Java).nameless())
+        .unwrap();
+    assert_eq!(bytes, [0x02, 0x00, 0x2a]);
+}
+
+#[test]
+fn reject_unrepresentable() {
+    assert!(matches!(
+        znbt::to_vec(&1i32),
+        Err(NbtError::UnexpectedKind { .. })
+    ));
+    let error = znbt::to_vec(&BTreeMap::from([(1, 2)])).unwrap_err();
+    assert!(matches!(error, NbtError::Unsupported(_)));
+    assert_eq!(
+        error.to_string(),
+        "map key other than string is not supported in NBT"
+    );
+    let error = znbt::to_vec(&BTreeMap::from([("a", vec![None::<i8>])])).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`None` outside of compound is not supported in NBT"
+    );
+    let error = znbt::to_vec(&BTreeMap::from([("a", ())])).unwrap_err();
+    assert_eq!(error.to_string(), "`()` is not supported in NBT");
+
+    // The unsigned integers are not bit-cast to the signed integers.
+    let error = znbt::to_vec(&BTreeMap::from([("a", 200u8)])).unwrap_err();
+    assert!(matches!(error, NbtError::Message(_)));
+    assert_eq!(error.to_string(), "integer `200` out of range of `i8`");
+    let error = znbt::to_vec(&BTreeMap::from([("a", u32::MAX)])).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "integer `4294967295` out of range of `i32`"
+    );
+    assert!(znbt::to_vec(&BTreeMap::from([("a", i64::MAX as u64)])).is_ok());
+    // A list cannot contain elements of different kinds.
+    assert!(matches!(
+        znbt::to_vec(&BTreeMap::from([("a", (1i8, 2i16))])),
+        Err(NbtError::UnexpectedKind { .. })
+    ));
+}
+
+#[test]
+fn deserialize_struct() {
+    let bytes = znbt::to_vec(&player()).unwrap();
+    assert_eq!(znbt::from_slice::<Player>(&bytes).unwrap(), player());
+    #[cfg(feature = "std")]
+    assert_eq!(znbt::from_reader::<_, Player>(&*bytes).unwrap(), player());
+
+    // Unknown entries are skipped, and missing options are none.
+    let mut compound = Compound::new();
+    compound.insert("id", "minecraft:dirt");
+    compound.insert("extra", List::Compound(vec![Compound::new()]));
+    compound.insert("count", 1i8);
+    let item: Item = znbt::from_slice(&encode(&compound)).unwrap();
+    assert_eq!(item.id, "minecraft:dirt");
+    assert_eq!(item.count, 1);
+}
+
+#[test]
+fn deserialize_borrowed_and_dynamic() {
+    #[derive(Deserialize)]
+    struct Borrowed<'a> {
+        name: &'a str,
+        flag: bool,
+        data: &'a [u8],
+        ints: Vec<i32>,
+    }
+
+    let mut compound = Compound::new();
+    compound.insert("name", "Steve");
+    compound.insert("flag", 1i8);
+    compound.insert("data", vec![1i8, -1]);
+    compound.insert("ints", vec![1i32, 2]);
+    let bytes = encode(&compound);
+
+    let borrowed: Borrowed = znbt::from_slice(&bytes).unwrap();
+    assert_eq!(borrowed.name, "Steve");
+    assert!(borrowed.flag);
+    assert_eq!(borrowed.data, [1, 0xff]);
+    assert_eq!(borrowed.ints, [1, 2]);
+
+    // Arrays are sequences, and compounds are maps.
+    let map: BTreeMap<String, Vec<i64>> =
+        znbt::from_slice(&encode(&Compound::from_iter([("la", vec![7i64])]))).unwrap();
+    assert_eq!(map["la"], [7]);
+}
+
+#[test]
+fn deserialize_enum_variants() {
+    let shapes = BTreeMap::from([
+        ("circle".to_owned(), Shape::Circle(1.0)),
+        ("point".to_owned(), Shape::Point(1, 2)),
+        ("rect".to_owned(), Shape::Rect { w: 3, h: 4 }),
+    ]);
+    let bytes = znbt::to_vec(&shapes).unwrap();
+    assert_eq!(
+        znbt::from_slice::<BTreeMap<_, Shape>>(&bytes).unwrap(),
+        shapes
+    );
+}
+
+#[test]
+fn deserialize_nameless_root() {
+    let mut input = &[0x02, 0x00, 0x2a][..];
+    let value = i16::deserialize(Deserializer::new(&mut input, Java).nameless()).unwrap();
+    assert_eq!(value, 42);
+    assert!(input.is_empty());
+}
+
+#[test]
+fn reject_mismatched() {
+    let bytes = encode(&Compound::from_iter([("id", 1i32), ("count", 1i32)]));
+    assert!(matches!(
+        znbt::from_slice::<Item>(&bytes),
+        Err(NbtError::Message(_))
+    ));
+    assert!(matches!(
+        znbt::from_slice::<BTreeMap<String, i32>>(&[0x0a, 0x00, 0x00]),
+        Err(NbtError::UnexpectedEof)
+    ));
+    // A list longer than the tuple is not silently truncated.
+    let bytes = encode(&Compound::from_iter([("a", List::Int(vec![1, 2, 3]))]));
+    assert!(znbt::from_slice::<BTreeMap<String, (i32, i32)>>(&bytes).is_err());
+}
+
+#[test]
+fn preserve_array_kinds() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entity {
+        #[serde(rename = "UUID", with = "znbt::array::int_array")]
+        uuid: [i32; 4],
+        #[serde(with = "znbt::array::long_array")]
+        heightmap: Vec<i64>,
+        #[serde(with = "znbt::array::byte_array")]
+        blocks: Vec<i8>,
+        #[serde(with = "znbt::array::int_array")]
+        empty: Vec<i32>,
+    }
+
+    let entity = Entity {
+        uuid: [1, 2, 3, 4],
+        heightmap: vec![-1],
+        blocks: vec![1, 2],
+        empty: vec![],
+    };
+    let mut compound = Compound::new();
+    compound.insert("UUID", vec![1i32, 2, 3, 4]);
+    compound.insert("heightmap", vec![-1i64]);
+    compound.insert("blocks", vec![1i8, 2]);
+    compound.insert("empty", Vec::<i32>::new());
+    let bytes = encode(&compound);
+
+    assert_eq!(znbt::to_vec(&entity).unwrap(), bytes);
+    assert_eq!(znbt::from_slice::<Entity>(&bytes).unwrap(), entity);
+
+    // Lists are accepted as well.
+    let mut compound = Compound::new();
+    compound.insert("UUID", List::Int(vec![1, 2, 3, 4]));
+    compound.insert("heightmap", List::Long(vec![-1]));
+    compound.insert("blocks", List::Byte(vec![1, 2]));
+    compound.insert("empty", List::End);
+    assert_eq!(
+        znbt::from_slice::<Entity>(&encode(&compound)).unwrap(),
+        entity
+    );
+
+    // The length of a fixed-size array is checked.
+    let compound = Compound::from_iter([("UUID", vec![1i32, 2, 3])]);
+    assert!(znbt::from_slice::<Entity>(&encode(&compound)).is_err());
+}
+
+#[test]
+fn kind_as_id() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Schema {
+        #[serde(with = "znbt::kind::id")]
+        kind: Kind,
+    }
+
+    let schema = Schema {
+        kind: Kind::IntArray,
+    };
+    let bytes = encode(&Compound::from_iter([("kind", 11i8)]));
+    assert_eq!(znbt::to_vec(&schema).unwrap(), bytes);
+    assert_eq!(znbt::from_slice::<Schema>(&bytes).unwrap(), schema);
+
+    for id in [0i8, 13] {
+        let bytes = encode(&Compound::from_iter([("kind", id)]));
+        let error = znbt::from_slice::<Schema>(&bytes).unwrap_err();
+        assert!(matches!(error, NbtError::Message(_)), "{error}");
+    }
+}
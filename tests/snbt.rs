@@ -0,0 +1,342 @@
+use znbt::snbt::{self, Dialect, Printer};
+use znbt::value::{Compound, List, Value, ValueRef};
+
+#[test]
+fn parse_numbers() {
+    let cases = [
+        ("1b", Value::Byte(1)),
+        ("-128B", Value::Byte(-128)),
+        ("300s", Value::Short(300)),
+        ("42", Value::Int(42)),
+        ("+7", Value::Int(7)),
+        ("9000000000L", Value::Long(9_000_000_000)),
+        ("1.5f", Value::Float(1.5)),
+        ("2F", Value::Float(2.0)),
+        ("3d", Value::Double(3.0)),
+        ("1.5", Value::Double(1.5)),
+        (".5", Value::Double(0.5)),
+        ("1.", Value::Double(1.0)),
+        ("-1.5e3", Value::Double(-1500.0)),
+        ("1e3d", Value::Double(1000.0)),
+        ("true", Value::Byte(1)),
+        ("false", Value::Byte(0)),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(snbt::parse(input).unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn parse_unquoted_strings() {
+    // Tokens that are not numbers are strings, including out of range numbers.
+    for input in [
+        "abc",
+        "1e3",
+        "01",
+        "128b",
+        "minecraft.stone",
+        "True",
+        "1.5b",
+    ] {
+        assert_eq!(snbt::parse(input).unwrap(), Value::from(input), "{input}");
+    }
+}
+
+#[test]
+fn parse_quoted_strings() {
+    assert_eq!(
+        snbt::parse(r#""a \"b\" \\ 'c'""#).unwrap(),
+        Value::from(r#"a "b" \ 'c'"#)
+    );
+    assert_eq!(snbt::parse(r"'it\'s'").unwrap(), Value::from("it's"));
+    assert_eq!(snbt::parse("\"日本語\"").unwrap(), Value::from("日本語"));
+    assert_eq!(snbt::parse("''").unwrap(), Value::from(""));
+}
+
+#[test]
+fn parse_compound() {
+    let input = r#"{name: "Steve", 'Health' : 20.0f, "a b": {}, Pos: [0.5d, 64.0d,],}"#;
+    let mut expected = Compound::new();
+    expected.insert("name", "Steve");
+    expected.insert("Health", 20.0f32);
+    expected.insert("a b", Compound::new());
+    expected.insert("Pos", List::Double(vec![0.5, 64.0]));
+    assert_eq!(input.parse::<Compound>().unwrap(), expected);
+}
+
+#[test]
+fn parse_lists_and_arrays() {
+    let cases = [
+        ("[]", Value::from(List::End)),
+        ("[1, 2]", Value::from(List::Int(vec![1, 2]))),
+        ("[[], [a]]", {
+            let mut list = List::new();
+            list.push(List::End).unwrap();
+            list.push(List::String(vec!["a".into()])).unwrap();
+            Value::from(list)
+        }),
+        ("[B; 1b, -2B]", Value::from(vec![1i8, -2])),
+        ("[I;]", Value::from(Vec::<i32>::new())),
+        ("[I; 1, 2, 3, 4]", Value::from(vec![1i32, 2, 3, 4])),
+        ("[L;1L,2l]", Value::from(vec![1i64, 2])),
+        ("[B]", Value::from(List::String(vec!["B".into()]))),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(snbt::parse(input).unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn reject_malformed() {
+    let cases = [
+        ("", 0),
+        ("{", 1),
+        ("{a}", 2),
+        ("{a:1 b:2}", 5),
+        ("{:1}", 1),
+        ("[1, 2b]", 4),
+        ("[I; 1, 2b]", 7),
+        ("[X; 1]", 1),
+        ("\"abc", 0),
+        (r#""\n""#, 1),
+        ("1 2", 2),
+    ];
+    for (input, position) in cases {
+        let error = snbt::parse(input).unwrap_err();
+        assert_eq!(error.position(), position, "{input}: {error}");
+    }
+    assert!("1".parse::<Compound>().is_err());
+    assert!(snbt::parse(&"[".repeat(1000)).is_err());
+}
+
+fn sample() -> Compound {
+    let mut compound = Compound::new();
+    compound.insert("id", "minecraft:stone");
+    compound.insert("Count", 1i8);
+    compound.insert("a b", Compound::new());
+    compound.insert("nums", List::Short(vec![1, -2]));
+    compound.insert("misc", {
+        let mut misc = Compound::new();
+        misc.insert("l", 5i64);
+        misc.insert("f", 1.5f32);
+        misc.insert("d", 1e20f64);
+        misc.insert("i", 7);
+        misc.insert("q", r#"say "hi""#);
+        misc.insert("e", List::End);
+        misc
+    });
+    compound.insert("ba", vec![1i8, 2]);
+    compound.insert("ia", vec![3i32]);
+    compound.insert("la", Vec::<i64>::new());
+    compound
+}
+
+#[test]
+fn print_compact() {
+    let expected = concat!(
+        r#"{id:"minecraft:stone",Count:1b,"a b":{},nums:[1s,-2s],"#,
+        r#"misc:{l:5L,f:1.5f,d:1.0E20d,i:7,q:'say "hi"',e:[]},"#,
+        r#"ba:[B;1B,2B],ia:[I;3],la:[L;]}"#,
+    );
+    assert_eq!(sample().to_string(), expected);
+    assert_eq!(expected.parse::<Compound>().unwrap(), sample());
+    assert_eq!(Value::from(r"a\b").to_string(), r#""a\\b""#);
+}
+
+#[test]
+fn print_pretty() {
+    let expected = "\
+{
+  Count: 1b,
+  \"a b\": {},
+  ba: [B; 1B, 2B],
+  ia: [I; 3],
+  id: \"minecraft:stone\",
+  la: [L;],
+  misc: {
+    d: 1.0E20d,
+    e: [],
+    f: 1.5f,
+    i: 7,
+    l: 5L,
+    q: 'say \"hi\"'
+  },
+  nums: [
+    1s,
+    -2s
+  ]
+}";
+    let printer = Printer::pretty().with_indent("  ").with_sorted_keys(true);
+    let printed = printer.to_string(ValueRef::Compound(&sample())).unwrap();
+    assert_eq!(printed, expected);
+    assert_eq!(printed.parse::<Compound>().unwrap().len(), sample().len());
+    assert_eq!(format!("{:#}", List::Int(vec![1])), "[\n    1\n]");
+}
+
+#[test]
+fn print_unquoted_strings() {
+    let printer = Printer::compact().with_unquoted_strings(true);
+    let strings = [
+        "minecraft:stone",
+        "stone",
+        "a.b-c_d+e",
+        "",
+        "1b",
+        "-x",
+        ".5",
+        "true",
+        "false",
+        "a b",
+        "é",
+    ];
+    let list = List::String(strings.iter().map(|&string| string.into()).collect());
+    let printed = printer.to_string(ValueRef::List(&list)).unwrap();
+    assert_eq!(
+        printed,
+        r#"["minecraft:stone",stone,a.b-c_d+e,"","1b","-x",".5","true","false","a b","é"]"#
+    );
+    assert_eq!(printed.parse::<Value>().unwrap(), Value::from(list.clone()));
+    assert_eq!(parse_modern(&printed).unwrap(), Value::from(list));
+    assert_eq!(Value::from("stone").to_string(), r#""stone""#);
+}
+
+#[test]
+fn print_floats() {
+    let cases = [
+        (Value::Float(0.0), "0.0f"),
+        (Value::Double(-0.0), "-0.0d"),
+        (Value::Float(1.0), "1.0f"),
+        (Value::Float(0.001), "0.001f"),
+        (Value::Double(0.1), "0.1d"),
+        (Value::Double(1234567.5), "1234567.5d"),
+        (Value::Float(1e7), "1.0E7f"),
+        (Value::Double(-1.5e20), "-1.5E20d"),
+        (Value::Double(1e-4), "1.0E-4d"),
+        (Value::Float(f32::MAX), "3.4028235E38f"),
+        (Value::Double(f64::MIN_POSITIVE), "2.2250738585072014E-308d"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(value.to_string(), expected);
+        assert_eq!(expected.parse::<Value>().unwrap(), value, "{expected}");
+        assert_eq!(parse_modern(expected).unwrap(), value, "{expected}");
+    }
+
+    // SNBT has no syntax for the non-finite floats.
+    let list = List::Float(vec![1.0, f32::NAN]);
+    assert!(Printer::compact().to_string(ValueRef::List(&list)).is_err());
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let mut string = String::new();
+        assert!(
+            Printer::pretty()
+                .print(&mut string, ValueRef::Double(value))
+                .is_err()
+        );
+    }
+}
+
+fn parse_modern(input: &str) -> Result<Value, snbt::SnbtError> {
+    snbt::parse_with(input, Dialect::V1_21_5)
+}
+
+#[test]
+fn parse_modern_numbers() {
+    let cases = [
+        ("0x1F", Value::Int(31)),
+        ("-0x10", Value::Int(-16)),
+        ("0b101", Value::Int(5)),
+        ("0b1b", Value::Byte(1)),
+        ("0b", Value::Byte(0)),
+        ("255ub", Value::Byte(-1)),
+        ("0xFFFFus", Value::Short(-1)),
+        ("-128sb", Value::Byte(-128)),
+        ("0xFFFFFFFFui", Value::Int(-1)),
+        ("0xFFFF_FFFF_FFFF_FFFFul", Value::Long(-1)),
+        ("1_000_000", Value::Int(1_000_000)),
+        ("7i", Value::Int(7)),
+        ("1_000.5", Value::Double(1000.5)),
+        ("1e3", Value::Double(1000.0)),
+        ("1.5F", Value::Float(1.5)),
+        ("true", Value::Byte(1)),
+        ("bool(0)", Value::Byte(0)),
+        ("bool(2.5)", Value::Byte(1)),
+        (
+            "uuid(f81d4fae-7dec-11d0-a765-00a0c91e6bf6)",
+            Value::from(vec![
+                0xf81d4faeu32 as i32,
+                0x7dec11d0,
+                0xa76500a0u32 as i32,
+                0xc91e6bf6u32 as i32,
+            ]),
+        ),
+        ("[B; 1, 2b, 0xFFub]", Value::from(vec![1i8, 2, -1])),
+        ("[L; 3000000000]", Value::from(vec![3_000_000_000i64])),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(parse_modern(input).unwrap(), expected, "{input}");
+    }
+}
+
+#[test]
+fn parse_modern_strings() {
+    let input = r#""\x41é\U0001F600\s\t\n\b\f\r\'\"\\""#;
+    let expected = "A\u{e9}\u{1F600} \t\n\u{8}\u{c}\r'\"\\";
+    assert_eq!(parse_modern(input).unwrap(), Value::from(expected));
+    assert_eq!(parse_modern("'\\\"'").unwrap(), Value::from("\""));
+    // Unquoted strings are the same as legacy, unless starting like numbers.
+    assert_eq!(parse_modern("abc-1.5").unwrap(), Value::from("abc-1.5"));
+
+    #[cfg(feature = "unicode-names")]
+    assert_eq!(
+        parse_modern(r#""\N{LATIN SMALL LETTER A}""#).unwrap(),
+        Value::from("a")
+    );
+    #[cfg(not(feature = "unicode-names"))]
+    assert!(parse_modern(r#""\N{LATIN SMALL LETTER A}""#).is_err());
+}
+
+#[test]
+fn parse_modern_heterogeneous_list() {
+    let expected = List::Mixed(vec![
+        Value::Int(1),
+        Value::from("a"),
+        Value::from(Compound::from_iter([("b", 2i8)])),
+        Value::from(Compound::from_iter([("", 3)])),
+    ]);
+    let input = r#"[1, "a", {b: 2b}, {"": 3}]"#;
+    let value = parse_modern(input).unwrap();
+    assert_eq!(value, Value::from(expected));
+    assert!(snbt::parse(input).is_err());
+    // The elements are printed unwrapped.
+    assert_eq!(value.to_string(), r#"[1,"a",{b:2b},{"":3}]"#);
+    assert_eq!(parse_modern(&value.to_string()).unwrap(), value);
+    // A list of the same kind is not mixed.
+    assert_eq!(
+        parse_modern("[1, 2]").unwrap(),
+        Value::from(List::Int(vec![1, 2]))
+    );
+}
+
+#[test]
+fn reject_modern_malformed() {
+    let cases = [
+        ("1e3x", 0),
+        ("128b", 0),
+        ("-1ub", 0),
+        ("0x", 0),
+        ("1__", 0),
+        ("1_.5", 0),
+        ("+", 0),
+        ("0xFFFFFFFF", 0),
+        ("[B; 1, 300]", 7),
+        ("foo(1)", 0),
+        ("bool(\"a\")", 0),
+        ("uuid(1)", 0),
+        (r#""\q""#, 1),
+        (r#""\x4""#, 1),
+        (r#""\uD800""#, 1),
+    ];
+    for (input, position) in cases {
+        let error = parse_modern(input).unwrap_err();
+        assert_eq!(error.position(), position, "{input}: {error}");
+    }
+}
@@ -0,0 +1,420 @@
+#![cfg(feature = "std")]
+
+use std::io::{BufRead, BufReader};
+
+use znbt::binary::{self, BedrockNetwork, Flavor, Java};
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::stream::{Event, Primitive, Reader, Writer};
+use znbt::value::{Compound, List, Value};
+
+fn all_kinds() -> Compound {
+    let mut compound = Compound::new();
+    compound.insert("b", -1i8);
+    compound.insert("s", 0x0102i16);
+    compound.insert("i", -300_000);
+    compound.insert("l", i64::MIN);
+    compound.insert("f", 1.5f32);
+    compound.insert("d", -1.0f64);
+    compound.insert("ba", vec![1i8, -2]);
+    compound.insert("str", "h\0i");
+    compound.insert("list", List::Int(vec![1, -2]));
+    compound.insert("empty", List::End);
+    let nested = Compound::from_iter([("id", "x")]);
+    compound.insert("nested", List::Compound(vec![nested, Compound::new()]));
+    compound.insert("lists", List::List(vec![List::Long(vec![7])]));
+    compound.insert("cpd", Compound::new());
+    compound.insert("ia", vec![-2i32, 1 << 20]);
+    compound.insert("la", vec![42i64]);
+    compound
+}
+
+/// Rebuilds the value of the next tag from the events.
+fn build<R: BufRead, F: Flavor>(reader: &mut Reader<R, F>) -> Value {
+    let event = reader.next_event().unwrap().unwrap();
+    match event {
+        Event::Value(_, primitive) => primitive_value(primitive),
+        Event::BeginCompound => {
+            let mut compound = Compound::new();
+            loop {
+                match reader.next_event().unwrap().unwrap() {
+                    Event::Key(key) => {
+                        let key = key.to_owned();
+                        compound.insert(key, build(reader));
+                    }
+                    Event::EndCompound => return Value::from(compound),
+                    event => panic!("unexpected {event:?}"),
+                }
+            }
+        }
+        Event::BeginList(kind, len) => {
+            let mut list = kind.map_or(List::End, List::with_kind);
+            for _ in 0..len {
+                list.push(build(reader)).unwrap();
+            }
+            assert_eq!(reader.next_event().unwrap(), Some(Event::EndList));
+            Value::from(list)
+        }
+        Event::BeginArray(kind, len) => {
+            let mut list = List::new();
+            for _ in 0..len {
+                list.push(build(reader)).unwrap();
+            }
+            assert_eq!(reader.next_event().unwrap(), Some(Event::EndArray));
+            array_value(kind, list)
+        }
+        event => panic!("unexpected {event:?}"),
+    }
+}
+
+fn primitive_value(primitive: Primitive<'_>) -> Value {
+    match primitive {
+        Primitive::Byte(value) => Value::from(value),
+        Primitive::Short(value) => Value::from(value),
+        Primitive::Int(value) => Value::from(value),
+        Primitive::Long(value) => Value::from(value),
+        Primitive::Float(value) => Value::from(value),
+        Primitive::Double(value) => Value::from(value),
+        Primitive::String(value) => Value::from(value),
+    }
+}
+
+fn array_value(kind: Kind, list: List) -> Value {
+    match (kind, list) {
+        (Kind::ByteArray, List::Byte(vec)) => Value::from(vec),
+        (Kind::IntArray, List::Int(vec)) => Value::from(vec),
+        (Kind::LongArray, List::Long(vec)) => Value::from(vec),
+        (_, List::End) => Value::new(kind),
+        (_, list) => panic!("unexpected {list:?}"),
+    }
+}
+
+#[test]
+fn hello_world_events() {
+    let input = b"\x0a\x00\x0bhello world\x08\x00\x04name\x00\x09Bananrama\x00";
+    let mut reader = Reader::new(&input[..], Java);
+    let mut events = Vec::new();
+    while let Some(event) = reader.next_event().unwrap() {
+        events.push(format!("{event:?}"));
+    }
+    assert_eq!(
+        events,
+        [
+            r#"Key("hello world")"#,
+            "BeginCompound",
+            r#"Key("name")"#,
+            r#"Value(String, String("Bananrama"))"#,
+            "EndCompound",
+        ]
+    );
+}
+
+#[test]
+fn array_and_list_events() {
+    let compound = Compound::from_iter([
+        ("a", Value::from(vec![7i32, 8])),
+        ("l", Value::from(List::End)),
+    ]);
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Java).unwrap();
+    let mut reader = Reader::new(&bytes[..], Java);
+    assert_eq!(reader.next_event().unwrap(), Some(Event::Key("")));
+    assert_eq!(reader.next_event().unwrap(), Some(Event::BeginCompound));
+    assert_eq!(reader.next_event().unwrap(), Some(Event::Key("a")));
+    assert_eq!(
+        reader.next_event().unwrap(),
+        Some(Event::BeginArray(Kind::IntArray, 2))
+    );
+    assert_eq!(reader.depth(), 2);
+    assert_eq!(
+        reader.next_event().unwrap(),
+        Some(Event::Value(Kind::Int, Primitive::Int(7)))
+    );
+    assert_eq!(
+        reader.next_event().unwrap(),
+        Some(Event::Value(Kind::Int, Primitive::Int(8)))
+    );
+    assert_eq!(reader.next_event().unwrap(), Some(Event::EndArray));
+    assert_eq!(reader.next_event().unwrap(), Some(Event::Key("l")));
+    assert_eq!(
+        reader.next_event().unwrap(),
+        Some(Event::BeginList(None, 0))
+    );
+    assert_eq!(reader.next_event().unwrap(), Some(Event::EndList));
+    assert_eq!(reader.next_event().unwrap(), Some(Event::EndCompound));
+    assert_eq!(reader.next_event().unwrap(), None);
+}
+
+#[test]
+fn concatenated_roots() {
+    let mut bytes = Vec::new();
+    for i in 0..3 {
+        let compound = Compound::from_iter([("i", i)]);
+        binary::write(&mut bytes, "root", &compound, Java).unwrap();
+    }
+    let mut reader = Reader::new(&bytes[..], Java);
+    for i in 0..3 {
+        assert_eq!(reader.next_event().unwrap(), Some(Event::Key("root")));
+        let value = build(&mut reader);
+        assert_eq!(value, Value::from(Compound::from_iter([("i", i)])));
+    }
+    assert_eq!(reader.next_event().unwrap(), None);
+}
+
+#[test]
+fn rebuild_all_kinds() {
+    let compound = all_kinds();
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Java).unwrap();
+    let mut reader = Reader::new(&bytes[..], Java);
+    assert_eq!(reader.next_event().unwrap(), Some(Event::Key("")));
+    assert_eq!(build(&mut reader), Value::from(compound.clone()));
+
+    // Every number spans the end of the buffer.
+    let mut bytes = Vec::new();
+    binary::write_nameless(&mut bytes, &Value::from(compound.clone()), BedrockNetwork).unwrap();
+    let stream = BufReader::with_capacity(1, &bytes[..]);
+    let mut reader = Reader::new(stream, BedrockNetwork).nameless();
+    assert_eq!(build(&mut reader), Value::from(compound));
+    assert_eq!(reader.next_event().unwrap(), None);
+}
+
+#[test]
+fn reject_malformed_streams() {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &all_kinds(), Java).unwrap();
+    for len in 1..bytes.len() {
+        let mut reader = Reader::new(&bytes[..len], Java);
+        let error = loop {
+            match reader.next_event() {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("{len}: unexpected end"),
+                Err(error) => break error,
+            }
+        };
+        assert!(matches!(error, NbtError::UnexpectedEof), "{len}: {error}");
+    }
+
+    let mut reader = Reader::new(&b"\x00"[..], Java);
+    assert!(reader.next_event().is_err());
+
+    // A named root tag must be a compound, like the other decoders.
+    let input = b"\x03\x00\x00\x00\x00\x00\x01";
+    let mut reader = Reader::new(&input[..], Java);
+    assert!(matches!(
+        reader.next_event(),
+        Err(NbtError::UnexpectedKind {
+            expected: Kind::Compound,
+            found: Some(Kind::Int)
+        })
+    ));
+    assert!(binary::read(&mut &input[..], Java).is_err());
+    let mut reader = Reader::new(&b"\x03\x00\x00\x00\x01"[..], Java).nameless();
+    assert!(matches!(
+        reader.next_event(),
+        Ok(Some(Event::Value(Kind::Int, Primitive::Int(1))))
+    ));
+
+    // A huge length is not allocated before the bytes are read.
+    let input = b"\x0a\xff\xff\xff\xff\x0fab";
+    let mut reader = Reader::new(&input[..], BedrockNetwork);
+    let error = reader.next_event().unwrap_err();
+    assert!(matches!(error, NbtError::UnexpectedEof), "{error}");
+
+    let mut input = vec![0x0a, 0x00, 0x00];
+    for _ in 0..NbtError::MAX_DEPTH {
+        input.extend([0x0a, 0x00, 0x00]);
+    }
+    let mut reader = Reader::new(&input[..], Java);
+    let error = loop {
+        if let Err(error) = reader.next_event() {
+            break error;
+        }
+    };
+    assert!(matches!(error, NbtError::DepthLimit));
+}
+
+#[test]
+fn write_all_kinds() {
+    let mut writer = Writer::new(Vec::new(), Java);
+    writer.begin_compound("").unwrap();
+    writer.field_byte("b", -1).unwrap();
+    writer.field_short("s", 0x0102).unwrap();
+    writer.field_int("i", -300_000).unwrap();
+    writer.field_long("l", i64::MIN).unwrap();
+    writer.field_float("f", 1.5).unwrap();
+    writer.field_double("d", -1.0).unwrap();
+    writer.field_byte_array("ba", &[1, -2]).unwrap();
+    writer.field_string("str", "h\0i").unwrap();
+    writer.begin_list("list", Some(Kind::Int), 2).unwrap();
+    writer.element_int(1).unwrap();
+    writer.element_int(-2).unwrap();
+    writer.end_list().unwrap();
+    writer.begin_list("empty", None, 0).unwrap();
+    writer.end_list().unwrap();
+    writer
+        .begin_list("nested", Some(Kind::Compound), 2)
+        .unwrap();
+    writer.begin_compound_element().unwrap();
+    writer.field_string("id", "x").unwrap();
+    writer.end_compound().unwrap();
+    writer.element_value(&Value::from(Compound::new())).unwrap();
+    writer.end_list().unwrap();
+    writer.begin_list("lists", Some(Kind::List), 1).unwrap();
+    writer.begin_list_element(Some(Kind::Long), 1).unwrap();
+    writer.element_long(7).unwrap();
+    writer.end_list().unwrap();
+    writer.end_list().unwrap();
+    writer.begin_compound("cpd").unwrap();
+    writer.end_compound().unwrap();
+    writer.field_int_array("ia", &[-2, 1 << 20]).unwrap();
+    writer.field_long_array("la", &[42]).unwrap();
+    assert_eq!(writer.depth(), 1);
+    writer.end_compound().unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let mut expected = Vec::new();
+    binary::write(&mut expected, "", &all_kinds(), Java).unwrap();
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn write_nameless_roots() {
+    let mut writer = Writer::new(Vec::new(), BedrockNetwork).nameless();
+    writer.element_string("hi").unwrap();
+    writer.begin_list_element(Some(Kind::Int), 1).unwrap();
+    writer.element_int(-1).unwrap();
+    writer.end_list().unwrap();
+    let bytes = writer.finish().unwrap();
+
+    let mut expected = Vec::new();
+    binary::write_nameless(&mut expected, &Value::from("hi"), BedrockNetwork).unwrap();
+    let list = Value::from(List::Int(vec![-1]));
+    binary::write_nameless(&mut expected, &list, BedrockNetwork).unwrap();
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn copy_events() {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "a", &all_kinds(), Java).unwrap();
+    binary::write(&mut bytes, "b", &Compound::new(), Java).unwrap();
+
+    // Copies the events, renaming the entries of the root compounds.
+    let mut reader = Reader::new(&bytes[..], Java);
+    let mut writer = Writer::new(Vec::new(), Java);
+    let mut name = None;
+    let mut array = None;
+    while let Some(event) = reader.next_event().unwrap() {
+        let result = match event {
+            Event::Key(key) if writer.depth() == 1 => {
+                name = Some(key.to_uppercase());
+                continue;
+            }
+            Event::Key(key) => {
+                name = Some(key.to_owned());
+                continue;
+            }
+            Event::Value(_, primitive) => match &mut array {
+                Some((_, _, list)) => {
+                    List::push(list, primitive_value(primitive)).unwrap();
+                    continue;
+                }
+                None => match name.take() {
+                    Some(name) => writer.field_value(&name, &primitive_value(primitive)),
+                    None => writer.element_value(&primitive_value(primitive)),
+                },
+            },
+            Event::BeginCompound => match name.take() {
+                Some(name) => writer.begin_compound(&name),
+                None => writer.begin_compound_element(),
+            },
+            Event::EndCompound => writer.end_compound(),
+            Event::BeginList(kind, len) => match name.take() {
+                Some(name) => writer.begin_list(&name, kind, len),
+                None => writer.begin_list_element(kind, len),
+            },
+            Event::EndList => writer.end_list(),
+            Event::BeginArray(kind, _) => {
+                array = Some((name.take(), kind, List::new()));
+                continue;
+            }
+            Event::EndArray => {
+                let (name, kind, list) = array.take().unwrap();
+                let value = array_value(kind, list);
+                match name {
+                    Some(name) => writer.field_value(&name, &value),
+                    None => writer.element_value(&value),
+                }
+            }
+        };
+        result.unwrap();
+    }
+    let bytes = writer.finish().unwrap();
+
+    let expected = Compound::from_iter(
+        all_kinds()
+            .into_iter()
+            .map(|(key, value)| (key.to_uppercase(), value)),
+    );
+    let mut input = &bytes[..];
+    assert_eq!(
+        binary::read(&mut input, Java).unwrap(),
+        ("a".into(), expected)
+    );
+    assert_eq!(
+        binary::read(&mut input, Java).unwrap(),
+        ("b".into(), Compound::new())
+    );
+    assert!(input.is_empty());
+}
+
+#[test]
+fn reject_mismatched_writes() {
+    let mut writer = Writer::new(Vec::new(), Java);
+    writer.begin_compound("").unwrap();
+    assert!(matches!(
+        writer.element_int(1),
+        Err(NbtError::InvalidState(_))
+    ));
+    assert_eq!(
+        writer.end_list().unwrap_err().to_string(),
+        "invalid writer state: end of list outside of list"
+    );
+    writer.begin_list("pos", Some(Kind::Double), 2).unwrap();
+    assert!(matches!(
+        writer.field_double("x", 1.0),
+        Err(NbtError::InvalidState(_))
+    ));
+    assert!(matches!(
+        writer.element_float(1.0),
+        Err(NbtError::UnexpectedKind {
+            expected: Kind::Double,
+            found: Some(Kind::Float)
+        })
+    ));
+    writer.element_double(1.0).unwrap();
+    assert!(matches!(writer.end_list(), Err(NbtError::Message(_))));
+    assert!(matches!(
+        writer.end_compound(),
+        Err(NbtError::InvalidState(_))
+    ));
+    writer.element_double(2.0).unwrap();
+    assert!(matches!(
+        writer.element_double(3.0),
+        Err(NbtError::Message(_))
+    ));
+    writer.end_list().unwrap();
+    assert!(matches!(writer.finish(), Err(NbtError::InvalidState(_))));
+
+    let mut writer = Writer::new(Vec::new(), Java);
+    assert!(matches!(
+        writer.element_int(1),
+        Err(NbtError::InvalidState(_))
+    ));
+    assert!(matches!(
+        writer.begin_list("", None, 1),
+        Err(NbtError::UnexpectedKind { found: None, .. })
+    ));
+    assert!(writer.finish().unwrap().is_empty());
+}
@@ -0,0 +1,236 @@
+use znbt::kind::Kind;
+use znbt::value::{Compound, List, MergeMode, Value, ValueRef};
+
+fn compound(input: &str) -> Compound {
+    input.parse().unwrap()
+}
+
+fn all_kinds() -> [(Value, Kind); 12] {
+    [
+        (Value::Byte(1), Kind::Byte),
+        (Value::Short(2), Kind::Short),
+        (Value::Int(3), Kind::Int),
+        (Value::Long(4), Kind::Long),
+        (Value::Float(5.0), Kind::Float),
+        (Value::Double(6.0), Kind::Double),
+        (Value::from(vec![7i8]), Kind::ByteArray),
+        (Value::from("8"), Kind::String),
+        (Value::from(List::Int(vec![9])), Kind::List),
+        (
+            Value::from(Compound::from_iter([("a", 10)])),
+            Kind::Compound,
+        ),
+        (Value::from(vec![11i32]), Kind::IntArray),
+        (Value::from(vec![12i64]), Kind::LongArray),
+    ]
+}
+
+#[test]
+fn value_kinds() {
+    for (value, kind) in all_kinds() {
+        assert_eq!(value.kind(), kind);
+        assert_eq!(value.as_borrowed().kind(), kind);
+        assert_eq!(value.as_borrowed().to_owned(), value);
+        assert_eq!(ValueRef::from(&value), value.as_borrowed());
+    }
+}
+
+#[test]
+fn default_values() {
+    for (_, kind) in all_kinds() {
+        let value = Value::new(kind);
+        assert_eq!(value.kind(), kind);
+        assert_eq!(value, Value::new(kind));
+    }
+    assert_eq!(Value::new(Kind::Int), Value::Int(0));
+    assert_eq!(Value::new(Kind::Double), Value::Double(0.0));
+    assert_eq!(Value::new(Kind::String).as_str(), Some(""));
+    assert_eq!(Value::new(Kind::ByteArray).as_byte_array(), Some(&[][..]));
+    assert_eq!(Value::new(Kind::List).as_list(), Some(&List::End));
+    assert_eq!(
+        Value::new(Kind::Compound).as_compound(),
+        Some(&Compound::new())
+    );
+}
+
+#[test]
+fn convert_primitives() {
+    assert_eq!(Value::from(-1i8), Value::Byte(-1));
+    assert_eq!(Value::from(true), Value::Byte(1));
+    assert_eq!(Value::from(false), Value::Byte(0));
+    assert_eq!(Value::from(2i16), Value::Short(2));
+    assert_eq!(Value::from(3i32), Value::Int(3));
+    assert_eq!(Value::from(4i64), Value::Long(4));
+    assert_eq!(Value::from(5.5f32), Value::Float(5.5));
+    assert_eq!(Value::from(6.5f64), Value::Double(6.5));
+    assert_eq!(Value::from("a"), Value::from(String::from("a")));
+    assert_eq!(Value::from(&[1i8, 2][..]), Value::from(vec![1i8, 2]));
+    assert_eq!(
+        Value::from(&[1i32][..]).as_borrowed(),
+        ValueRef::IntArray(&[1])
+    );
+    assert_eq!(
+        Value::from(&[1i64][..]).as_borrowed(),
+        ValueRef::LongArray(&[1])
+    );
+
+    assert_eq!(Value::Byte(2).as_bool(), Some(true));
+    assert_eq!(Value::Byte(0).as_bool(), Some(false));
+    assert_eq!(Value::Byte(1).as_byte(), Some(1));
+    assert_eq!(Value::Short(1).as_byte(), None);
+    assert_eq!(Value::Short(1).as_short(), Some(1));
+    assert_eq!(Value::Int(1).as_int(), Some(1));
+    assert_eq!(Value::Int(1).as_long(), None);
+    assert_eq!(Value::Long(1).as_long(), Some(1));
+    assert_eq!(Value::Float(1.0).as_float(), Some(1.0));
+    assert_eq!(Value::Double(1.0).as_double(), Some(1.0));
+    assert_eq!(Value::Double(1.0).as_float(), None);
+}
+
+#[test]
+fn compare_and_debug() {
+    // Values of different kinds are never equal, even if numerically equal.
+    assert_ne!(Value::Int(1), Value::Long(1));
+    assert_ne!(Value::from(vec![1i8]), Value::from(List::Byte(vec![1])));
+    assert_eq!(Value::from("a"), Value::from("a"));
+    assert_ne!(Value::from("a"), Value::from("b"));
+
+    assert_eq!(format!("{:?}", Value::Int(1)), "Int(1)");
+    assert_eq!(format!("{:?}", Value::from("a")), r#"String("a")"#);
+    assert_eq!(
+        format!("{:?}", Value::from(vec![1i32, 2])),
+        "IntArray([1, 2])"
+    );
+    assert_eq!(format!("{:?}", ValueRef::Long(1)), "Long(1)");
+}
+
+#[test]
+fn list_of_one_kind() {
+    let mut list = List::new();
+    assert_eq!(list.kind(), None);
+    list.push(Value::Int(1)).unwrap();
+    list.push(Value::Int(2)).unwrap();
+    assert_eq!(list.kind(), Some(Kind::Int));
+    assert_eq!(list.push(Value::Long(3)), Err(Value::Long(3)));
+    assert_eq!(list.len(), 2);
+
+    assert_eq!(list.pop(), Some(Value::Int(2)));
+    assert_eq!(list.pop(), Some(Value::Int(1)));
+    assert_eq!(list.pop(), None);
+    // The element kind is kept even if the list becomes empty.
+    assert_eq!(list.kind(), Some(Kind::Int));
+    assert_eq!(list.push("a"), Err(Value::from("a")));
+    assert_eq!(List::new().kind(), None);
+
+    let elements = vec![Value::Int(1), Value::Byte(2)];
+    assert_eq!(List::try_from(elements), Err(Value::Byte(2)));
+    assert_eq!(List::try_from(Vec::<Value>::new()), Ok(List::End));
+}
+
+#[test]
+fn compound_entries() {
+    let mut compound = Compound::from_iter([("b", 1), ("a", 2)]);
+    assert_eq!(compound.insert("c", 3), None);
+    assert_eq!(compound.insert("b", 4i8), Some(Value::Int(1)));
+    // The entries are kept in insertion order, even if replaced.
+    assert_eq!(compound.keys().collect::<Vec<_>>(), ["b", "a", "c"]);
+    assert_eq!(compound.get("b"), Some(&Value::Byte(4)));
+
+    assert_eq!(compound.remove("a"), Some(Value::Int(2)));
+    assert_eq!(compound.remove("a"), None);
+    assert!(!compound.contains_key("a"));
+    assert_eq!(compound.keys().collect::<Vec<_>>(), ["b", "c"]);
+
+    // The order of the entries is compared.
+    let reversed = Compound::from_iter([("c", Value::Int(3)), ("b", Value::Byte(4))]);
+    assert_ne!(compound, reversed);
+    assert_eq!(compound.len(), reversed.len());
+}
+
+fn item() -> Compound {
+    compound(
+        r#"{
+            id: "minecraft:diamond_sword",
+            count: 1,
+            components: {
+                "minecraft:damage": 5,
+                "minecraft:enchantments": {levels: {"minecraft:sharpness": 3}},
+            },
+        }"#,
+    )
+}
+
+#[test]
+fn merge_nested_compounds() {
+    let patch = compound(
+        r#"{
+            count: 2L,
+            components: {
+                "minecraft:enchantments": {levels: {"minecraft:unbreaking": 1}},
+                "minecraft:lore": ["a"],
+            },
+            tag: {},
+        }"#,
+    );
+    let mut item = item();
+    assert!(item.merge(&patch));
+    assert_eq!(
+        item,
+        compound(
+            r#"{
+                id: "minecraft:diamond_sword",
+                count: 2L,
+                components: {
+                    "minecraft:damage": 5,
+                    "minecraft:enchantments": {
+                        levels: {"minecraft:sharpness": 3, "minecraft:unbreaking": 1},
+                    },
+                    "minecraft:lore": ["a"],
+                },
+                tag: {},
+            }"#
+        )
+    );
+    assert!(!item.merge(&patch));
+    assert!(!item.merge(&Compound::new()));
+}
+
+#[test]
+fn merge_replaces_other_values() {
+    let mut item = item();
+    assert!(item.merge(&compound(r#"{count: [1, 2], components: "none"}"#)));
+    assert_eq!(
+        item,
+        compound(r#"{id: "minecraft:diamond_sword", count: [1, 2], components: "none"}"#)
+    );
+    // A list is replaced as a whole rather than merged.
+    assert!(item.merge(&compound("{count: [3]}")));
+    assert_eq!(item.get("count"), Some(&Value::from(List::Int(vec![3]))));
+    assert!(item.merge(&compound("{components: {a: 1}}")));
+    assert_eq!(
+        item.get("components"),
+        Some(&Value::from(compound("{a: 1}")))
+    );
+}
+
+#[test]
+fn replace_nested_compounds() {
+    let patch = compound(
+        r#"{components: {"minecraft:enchantments": {levels: {"minecraft:unbreaking": 1}}}}"#,
+    );
+    let mut item = item();
+    assert!(item.merge_with(&patch, MergeMode::Replace));
+    assert_eq!(
+        item,
+        compound(
+            r#"{
+                id: "minecraft:diamond_sword",
+                count: 1,
+                components: {"minecraft:enchantments": {levels: {"minecraft:unbreaking": 1}}},
+            }"#
+        )
+    );
+    assert!(!item.merge_with(&patch, MergeMode::Replace));
+    assert!(!item.merge_with(&patch, MergeMode::Merge));
+    assert_eq!(MergeMode::default(), MergeMode::Merge);
+}
@@ -0,0 +1,195 @@
+use znbt::binary::{self, Java};
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::value::{Compound, List, Value};
+use znbt::view::{self, View};
+
+fn chunk() -> Compound {
+    let mut compound = Compound::new();
+    compound.insert("DataVersion", 3953);
+    compound.insert("Status", "minecraft:full");
+    compound.insert("blocks", vec![1i8, -2]);
+    compound.insert("heightmap", vec![-1i64, 0x0102030405060708]);
+    compound.insert("UUID", vec![1i32, 2, 3, 4]);
+    let section = |y: i8| Compound::from_iter([("Y", Value::from(y)), ("name", Value::from("\0"))]);
+    compound.insert("sections", List::Compound(vec![section(-4), section(5)]));
+    compound.insert("pos", List::Double(vec![0.5, 64.0, -0.5]));
+    compound.insert("empty", List::End);
+    compound.insert("mixed", List::Mixed(vec![Value::Int(1), Value::from("a")]));
+    compound
+}
+
+fn encode(compound: &Compound) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "root", compound, Java).unwrap();
+    bytes
+}
+
+#[test]
+fn view_entries() {
+    let bytes = encode(&chunk());
+    let mut input = &bytes[..];
+    let (name, root) = view::read(&mut input).unwrap();
+    assert!(input.is_empty());
+    assert_eq!(name, "root");
+    assert_eq!(root.len(), 9);
+
+    assert_eq!(root.get("DataVersion"), Some(View::Int(3953)));
+    assert_eq!(root.get("missing"), None);
+    let status = root.get("Status").and_then(|v| v.as_str()).unwrap();
+    assert_eq!(status, "minecraft:full");
+    assert_eq!(status.as_bytes(), b"minecraft:full");
+    assert_eq!(
+        root.get("blocks").and_then(|v| v.as_byte_array()),
+        Some(&[1, -2][..])
+    );
+
+    let heightmap = root
+        .get("heightmap")
+        .and_then(|v| v.as_long_array())
+        .unwrap();
+    assert_eq!(heightmap.len(), 2);
+    assert_eq!(heightmap.get(1), Some(0x0102030405060708));
+    assert_eq!(heightmap.get(2), None);
+    assert_eq!(heightmap.to_vec(), [-1, 0x0102030405060708]);
+    let uuid = root.get("UUID").and_then(|v| v.as_int_array()).unwrap();
+    assert_eq!(uuid.iter().rev().collect::<Vec<_>>(), [4, 3, 2, 1]);
+    assert_eq!(uuid.get(usize::MAX / 4), None);
+    assert_eq!(heightmap.get(usize::MAX), None);
+
+    let keys: Vec<_> = root.iter().map(|(key, _)| key.to_string()).collect();
+    assert_eq!(keys[..3], ["DataVersion", "Status", "blocks"]);
+}
+
+#[test]
+fn view_lists() {
+    let bytes = encode(&chunk());
+    let (_, root) = view::read(&mut &bytes[..]).unwrap();
+
+    let sections = root.get("sections").and_then(|v| v.as_list()).unwrap();
+    assert_eq!(sections.kind(), Some(Kind::Compound));
+    assert_eq!(sections.len(), 2);
+    let ys: Vec<_> = sections
+        .iter()
+        .map(|section| section.as_compound().unwrap().get("Y").unwrap())
+        .collect();
+    assert_eq!(ys, [View::Byte(-4), View::Byte(5)]);
+    let second = sections.get(1).and_then(|v| v.as_compound()).unwrap();
+    let name = second.get("name").and_then(|v| v.as_str()).unwrap();
+    // The null character is encoded in two bytes.
+    assert_eq!(name.as_bytes(), [0xc0, 0x80]);
+    assert_eq!(name.to_str().unwrap(), "\0");
+    assert_eq!(name, "\0");
+
+    let pos = root.get("pos").and_then(|v| v.as_list()).unwrap();
+    assert_eq!(pos.get(2), Some(View::Double(-0.5)));
+    assert_eq!(pos.get(3), None);
+
+    let empty = root.get("empty").and_then(|v| v.as_list()).unwrap();
+    assert_eq!(empty.kind(), None);
+    assert!(empty.is_empty());
+    assert_eq!(empty.iter().next(), None);
+}
+
+#[test]
+fn view_to_owned() {
+    let bytes = encode(&chunk());
+    let (_, root) = view::read(&mut &bytes[..]).unwrap();
+    assert_eq!(root.to_owned().unwrap(), chunk());
+
+    let input = b"\x08\x00\x02hi";
+    let value = view::read_nameless(&mut &input[..]).unwrap();
+    assert_eq!(value.kind(), Kind::String);
+    assert_eq!(value.to_owned().unwrap(), Value::from("hi"));
+
+    // The strings are validated on decoding.
+    let input = b"\x08\x00\x01\xff";
+    let value = view::read_nameless(&mut &input[..]).unwrap();
+    assert!(matches!(value.to_owned(), Err(NbtError::InvalidString)));
+}
+
+#[test]
+fn reject_malformed_views() {
+    let bytes = encode(&chunk());
+    for len in 0..bytes.len() {
+        let error = view::read(&mut &bytes[..len]).unwrap_err();
+        assert!(matches!(error, NbtError::UnexpectedEof), "{len}: {error}");
+    }
+
+    let cases: &[&[u8]] = &[
+        b"\x08\x00\x00",
+        b"\x0a\x00\x00\x0d\x00\x00\x00",
+        b"\x0a\x00\x00\x09\x00\x01l\x00\x00\x00\x00\x01\x00",
+        b"\x0a\x00\x00\x0b\x00\x01a\xff\xff\xff\xff\x00",
+    ];
+    for input in cases {
+        assert!(view::read(&mut &input[..]).is_err(), "{input:?}");
+    }
+
+    let mut input = vec![0x0a, 0x00, 0x00];
+    for _ in 0..NbtError::MAX_DEPTH {
+        input.extend([0x0a, 0x00, 0x00]);
+    }
+    let error = view::read(&mut &input[..]).unwrap_err();
+    assert!(matches!(error, NbtError::DepthLimit));
+}
+
+#[test]
+fn skip_payloads() {
+    let compound = chunk();
+    let bytes = encode(&compound);
+    // The root tag ID and the name.
+    let mut input = &bytes[7..];
+    view::skip(Kind::Compound, &mut input).unwrap();
+    assert!(input.is_empty());
+
+    for (_, value) in &compound {
+        let mut bytes = Vec::new();
+        binary::write_nameless(&mut bytes, value, Java).unwrap();
+        bytes.extend_from_slice(b"rest");
+        let mut input = &bytes[1..];
+        view::skip(value.kind(), &mut input).unwrap();
+        assert_eq!(input, b"rest");
+    }
+
+    let error = view::skip(Kind::LongArray, &mut &b"\x00\x00\x00\x02\x00"[..]).unwrap_err();
+    assert!(matches!(error, NbtError::UnexpectedEof));
+}
+
+#[test]
+fn find_paths() {
+    let mut level = Compound::new();
+    level.insert("xPos", 3);
+    level.insert("Sections", List::Compound(vec![Compound::new()]));
+    let mut root = Compound::new();
+    root.insert("DataVersion", 1343);
+    root.insert("Level", level);
+    let bytes = encode(&root);
+
+    let (kind, range) = view::find_path(&bytes, &["Level", "Sections"])
+        .unwrap()
+        .unwrap();
+    assert_eq!(kind, Kind::List);
+    assert_eq!(range.end, bytes.len() - 2);
+    assert_eq!(&bytes[range.clone()], b"\x0a\x00\x00\x00\x01\x00");
+    let sections = view::read_payload(kind, &mut &bytes[range]).unwrap();
+    assert_eq!(sections.as_list().map(|list| list.len()), Some(1));
+
+    let (kind, range) = view::find_path(&bytes, &[]).unwrap().unwrap();
+    assert_eq!((kind, range), (Kind::Compound, 7..bytes.len()));
+
+    assert_eq!(view::find_path(&bytes, &["Level", "zPos"]).unwrap(), None);
+    assert_eq!(
+        view::find_path(&bytes, &["DataVersion", "x"]).unwrap(),
+        None
+    );
+
+    // The input after the target is not read.
+    let (_, range) = view::find_path(&bytes, &["DataVersion"]).unwrap().unwrap();
+    let truncated = &bytes[..range.end];
+    assert_eq!(
+        view::find_path(truncated, &["DataVersion"]).unwrap(),
+        Some((Kind::Int, range))
+    );
+    assert!(view::find_path(truncated, &["Level"]).is_err());
+}
@@ -0,0 +1,301 @@
+use std::mem;
+
+use znbt::binary::{self, Java};
+use znbt::error::NbtError;
+use znbt::kind::Kind;
+use znbt::value::{Compound, List, ThinStr, Value};
+use znbt::view;
+use znbt::visit::{Path, Segment, Visitor, VisitorMut, Walk, WalkMut};
+
+fn chunk() -> Compound {
+    let mut compound = Compound::new();
+    compound.insert("DataVersion", 3953);
+    compound.insert("Status", "minecraft:full");
+    compound.insert("blocks", vec![1i8, -2]);
+    compound.insert("heightmap", vec![-1i64]);
+    compound.insert("UUID", vec![1i32, 2]);
+    let section = |y: i8| Compound::from_iter([("Y", Value::from(y)), ("name", Value::from("a"))]);
+    compound.insert("sections", List::Compound(vec![section(-4), section(5)]));
+    compound.insert("pos", List::Double(vec![0.5, -0.5]));
+    compound.insert("lists", List::List(vec![List::Short(vec![7]), List::End]));
+    compound.insert(
+        "mixed",
+        List::Mixed(vec![Value::Int(1), Value::from(Compound::new())]),
+    );
+    compound.insert("misc", Compound::from_iter([("f", 1.5f32)]));
+    compound.insert("long", 42i64);
+    compound
+}
+
+/// Records every callback.
+#[derive(Default)]
+struct Log(Vec<String>);
+
+impl Visitor for Log {
+    fn visit_byte(&mut self, path: &Path, value: i8) {
+        self.0.push(format!("{path} = {value}b"));
+    }
+
+    fn visit_short(&mut self, path: &Path, value: i16) {
+        self.0.push(format!("{path} = {value}s"));
+    }
+
+    fn visit_int(&mut self, path: &Path, value: i32) {
+        self.0.push(format!("{path} = {value}"));
+    }
+
+    fn visit_long(&mut self, path: &Path, value: i64) {
+        self.0.push(format!("{path} = {value}L"));
+    }
+
+    fn visit_float(&mut self, path: &Path, value: f32) {
+        self.0.push(format!("{path} = {value}f"));
+    }
+
+    fn visit_double(&mut self, path: &Path, value: f64) {
+        self.0.push(format!("{path} = {value}d"));
+    }
+
+    fn visit_byte_array(&mut self, path: &Path, value: &[i8]) {
+        self.0.push(format!("{path} = B{value:?}"));
+    }
+
+    fn visit_string(&mut self, path: &Path, value: &str) {
+        self.0.push(format!("{path} = {value:?}"));
+    }
+
+    fn visit_int_array(&mut self, path: &Path, value: &[i32]) {
+        self.0.push(format!("{path} = I{value:?}"));
+    }
+
+    fn visit_long_array(&mut self, path: &Path, value: &[i64]) {
+        self.0.push(format!("{path} = L{value:?}"));
+    }
+
+    fn enter_list(&mut self, path: &Path, kind: Option<Kind>, len: usize) -> bool {
+        self.0.push(format!("{path} = [{kind:?}; {len}"));
+        true
+    }
+
+    fn leave_list(&mut self, path: &Path) {
+        self.0.push(format!("{path} ]"));
+    }
+
+    fn enter_compound(&mut self, path: &Path, len: usize) -> bool {
+        self.0.push(format!("{path} = {{{len}"));
+        true
+    }
+
+    fn leave_compound(&mut self, path: &Path) {
+        self.0.push(format!("{path} }}"));
+    }
+}
+
+#[test]
+fn walk_owned_and_view() {
+    let compound = chunk();
+    let mut log = Log::default();
+    compound.walk(&mut log).unwrap();
+    assert_eq!(
+        log.0,
+        [
+            " = {11",
+            "DataVersion = 3953",
+            r#"Status = "minecraft:full""#,
+            "blocks = B[1, -2]",
+            "heightmap = L[-1]",
+            "UUID = I[1, 2]",
+            "sections = [Some(Compound); 2",
+            "sections[0] = {2",
+            "sections[0].Y = -4b",
+            r#"sections[0].name = "a""#,
+            "sections[0] }",
+            "sections[1] = {2",
+            "sections[1].Y = 5b",
+            r#"sections[1].name = "a""#,
+            "sections[1] }",
+            "sections ]",
+            "pos = [Some(Double); 2",
+            "pos[0] = 0.5d",
+            "pos[1] = -0.5d",
+            "pos ]",
+            "lists = [Some(List); 2",
+            "lists[0] = [Some(Short); 1",
+            "lists[0][0] = 7s",
+            "lists[0] ]",
+            "lists[1] = [None; 0",
+            "lists[1] ]",
+            "lists ]",
+            "mixed = [Some(Compound); 2",
+            "mixed[0] = 1",
+            "mixed[1] = {0",
+            "mixed[1] }",
+            "mixed ]",
+            "misc = {1",
+            "misc.f = 1.5f",
+            "misc }",
+            "long = 42L",
+            " }",
+        ]
+    );
+
+    let mut bytes = Vec::new();
+    binary::write(&mut bytes, "", &compound, Java).unwrap();
+    let (_, root) = view::read(&mut &bytes[..]).unwrap();
+    let mut view_log = Log::default();
+    root.walk(&mut view_log).unwrap();
+    assert_eq!(view_log.0, log.0);
+
+    let mut value_log = Log::default();
+    Value::from(compound).walk(&mut value_log).unwrap();
+    assert_eq!(value_log.0, log.0);
+}
+
+#[test]
+fn skip_subtrees() {
+    /// Counts the tags outside of lists.
+    #[derive(Default)]
+    struct Count(usize);
+
+    impl Visitor for Count {
+        fn visit_int(&mut self, _: &Path, _: i32) {
+            self.0 += 1;
+        }
+
+        fn enter_list(&mut self, _: &Path, _: Option<Kind>, _: usize) -> bool {
+            false
+        }
+
+        fn leave_list(&mut self, _: &Path) {
+            panic!("skipped list is left");
+        }
+
+        fn enter_compound(&mut self, _: &Path, _: usize) -> bool {
+            self.0 += 1;
+            true
+        }
+    }
+
+    let value: Value = "{a: 1, b: [2, 3], c: {d: 4, e: [{f: 5}]}}".parse().unwrap();
+    let mut count = Count::default();
+    value.walk(&mut count).unwrap();
+    assert_eq!(count.0, 4);
+
+    let mut bytes = Vec::new();
+    binary::write_nameless(&mut bytes, &value, Java).unwrap();
+    let mut count = Count::default();
+    view::read_nameless(&mut &bytes[..])
+        .unwrap()
+        .walk(&mut count)
+        .unwrap();
+    assert_eq!(count.0, 4);
+}
+
+#[test]
+fn modify_in_place() {
+    /// Redacts the names, renames the keys to lower case, doubles the ints, and
+    /// clears the lists of bytes.
+    struct Redact;
+
+    impl VisitorMut for Redact {
+        fn visit_int(&mut self, _: &Path, value: &mut i32) {
+            *value *= 2;
+        }
+
+        fn visit_string(&mut self, path: &Path, value: &mut ThinStr) {
+            if path.last() == Some(Segment::Key("name")) {
+                *value = ThinStr::from("***");
+            }
+        }
+
+        fn enter_compound(&mut self, _: &Path, compound: &mut Compound) -> bool {
+            *compound = mem::take(compound)
+                .into_iter()
+                .map(|(key, value)| (key.to_lowercase(), value))
+                .collect();
+            true
+        }
+
+        fn leave_list(&mut self, _: &Path, list: &mut List) {
+            if list.kind() == Some(Kind::Byte) {
+                list.clear();
+            }
+        }
+    }
+
+    let mut compound = chunk();
+    compound.walk_mut(&mut Redact);
+    let expected: Value = r#"{
+        dataversion: 7906,
+        status: "minecraft:full",
+        blocks: [B; 1b, -2b],
+        heightmap: [L; -1L],
+        uuid: [I; 1, 2],
+        sections: [{y: -4b, name: "***"}, {y: 5b, name: "***"}],
+        pos: [0.5d, -0.5d],
+        lists: [[7s], []],
+        mixed: [],
+        misc: {f: 1.5f},
+        long: 42L,
+    }"#
+    .parse()
+    .unwrap();
+    let mut expected = expected.as_compound().unwrap().clone();
+    expected.insert(
+        "mixed",
+        List::Mixed(vec![Value::Int(2), Value::from(Compound::new())]),
+    );
+    assert_eq!(compound, expected);
+
+    let mut list = List::Byte(vec![1]);
+    list.walk_mut(&mut Redact);
+    assert_eq!(list, List::End);
+}
+
+#[test]
+fn display_paths() {
+    let mut path = Path::new();
+    assert_eq!(path.to_string(), "");
+    path.push_key("Items");
+    path.push_index(0);
+    path.push_key("tag");
+    path.push_key("display name");
+    path.push_index(2);
+    assert_eq!(path.to_string(), r#"Items[0].tag."display name"[2]"#);
+    assert_eq!(path.len(), 5);
+    assert_eq!(path.get(3), Some(Segment::Key("display name")));
+    assert_eq!(path.last(), Some(Segment::Index(2)));
+
+    assert!(path.pop());
+    assert!(path.pop());
+    path.push_key("a.b");
+    path.push_key("");
+    assert_eq!(path.to_string(), r#"Items[0].tag."a.b"."""#);
+    let segments: Vec<_> = path.iter().collect();
+    assert_eq!(
+        segments,
+        [
+            Segment::Key("Items"),
+            Segment::Index(0),
+            Segment::Key("tag"),
+            Segment::Key("a.b"),
+            Segment::Key(""),
+        ]
+    );
+    while path.pop() {}
+    assert!(path.is_empty());
+
+    let mut path = Path::new();
+    path.push_index(1);
+    path.push_key("x");
+    assert_eq!(path.to_string(), "[1].x");
+}
+
+#[test]
+fn reject_invalid_strings() {
+    // A compound with the string "\xff".
+    let bytes = b"\x0a\x00\x00\x08\x00\x01s\x00\x01\xff\x00";
+    let (_, root) = view::read(&mut &bytes[..]).unwrap();
+    let error = root.walk(&mut Log::default()).unwrap_err();
+    assert!(matches!(error, NbtError::InvalidString));
+}